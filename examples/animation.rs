@@ -14,6 +14,8 @@ use lvgl::misc::anim::{AnimRepeatCount, Animation};
 use lvgl::style::Style;
 use lvgl::widgets::{Btn, Label};
 use lvgl::{Align, Color, Display, DrawBuffer, LvError, Part, Widget};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use std::thread::sleep;
 use std::time::Duration;
 use std::time::Instant;
@@ -23,23 +25,31 @@ fn main() -> Result<(), LvError> {
     const HOR_RES: u32 = 240;
     const VER_RES: u32 = 240;
 
-    let mut sim_display: SimulatorDisplay<Rgb565> =
-        SimulatorDisplay::new(Size::new(HOR_RES, VER_RES));
+    let sim_display = Rc::new(RefCell::new(SimulatorDisplay::<Rgb565>::new(Size::new(
+        HOR_RES, VER_RES,
+    ))));
 
     let output_settings = OutputSettingsBuilder::new().scale(2).build();
     let mut window = Window::new("Button Example", &output_settings);
 
     let buffer = DrawBuffer::<{ (HOR_RES * VER_RES) as usize }>::default();
 
-    let display = Display::register(buffer, HOR_RES, VER_RES, |refresh| {
-        sim_display.draw_iter(refresh.as_pixels()).unwrap();
+    let flushed_display = sim_display.clone();
+    let display = Display::register(buffer, HOR_RES, VER_RES, move |refresh| {
+        flushed_display
+            .borrow_mut()
+            .draw_iter(refresh.as_pixels())
+            .unwrap();
     })?;
 
     // Define the initial state of your input
-    let mut latest_touch_status = PointerInputData::Touch(Point::new(0, 0)).released().once();
+    let latest_touch_status = Rc::new(Cell::new(
+        PointerInputData::Touch(Point::new(0, 0)).released().once(),
+    ));
 
     // Register a new input device that's capable of reading the current state of the input
-    let _touch_screen = Pointer::register(|| latest_touch_status, &display)?;
+    let status = latest_touch_status.clone();
+    let _touch_screen = Pointer::register(move || status.get(), &display)?;
 
     // Create screen and widgets
     let mut screen = display.get_scr_act()?;
@@ -61,9 +71,10 @@ fn main() -> Result<(), LvError> {
     })?;
     anim.set_repeat_count(AnimRepeatCount::Infinite);
     anim.start();
-    button.on_event(|_btn, event| {
+    button.on_event(move |btn, event, _| {
         println!("Button received event: {:?}", event);
         if let lvgl::Event::Clicked = event {
+            let mut btn_lbl: Label = btn.get_child(0).and_then(|lbl| lbl.downcast()).unwrap();
             if btn_state {
                 let nt = CString::new("Click me!").unwrap();
                 btn_lbl.set_text(nt.as_c_str()).unwrap();
@@ -78,7 +89,7 @@ fn main() -> Result<(), LvError> {
     'running: loop {
         let start = Instant::now();
         lvgl::task_handler();
-        window.update(&sim_display);
+        window.update(&sim_display.borrow());
 
         let events = window.events().peekable();
 
@@ -89,13 +100,13 @@ fn main() -> Result<(), LvError> {
                     point,
                 } => {
                     println!("Clicked on: {:?}", point);
-                    latest_touch_status = PointerInputData::Touch(point).pressed().once();
+                    latest_touch_status.set(PointerInputData::Touch(point).pressed().once());
                 }
                 SimulatorEvent::MouseButtonUp {
                     mouse_btn: _,
                     point,
                 } => {
-                    latest_touch_status = PointerInputData::Touch(point).released().once();
+                    latest_touch_status.set(PointerInputData::Touch(point).released().once());
                 }
                 SimulatorEvent::Quit => break 'running,
                 _ => {}
@@ -9,6 +9,8 @@ use lvgl::style::Style;
 use lvgl::widgets::{Arc, Label};
 use lvgl::{Align, Color, Display, DrawBuffer, LvError, Part, Widget};
 use lvgl_sys;
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::thread::sleep;
 use std::time::Duration;
 use std::time::Instant;
@@ -35,16 +37,21 @@ fn main() -> Result<(), LvError> {
     const VER_RES: u32 = 240;
 
     println!("meminfo init: {:?}", mem_info());
-    let mut sim_display: SimulatorDisplay<Rgb565> =
-        SimulatorDisplay::new(Size::new(HOR_RES, VER_RES));
+    let sim_display = Rc::new(RefCell::new(SimulatorDisplay::<Rgb565>::new(Size::new(
+        HOR_RES, VER_RES,
+    ))));
 
     let output_settings = OutputSettingsBuilder::new().scale(1).build();
     let mut window = Window::new("Arc Example", &output_settings);
 
     let buffer = DrawBuffer::<{ (HOR_RES * VER_RES) as usize }>::default();
 
-    let display = Display::register(buffer, HOR_RES, VER_RES, |refresh| {
-        sim_display.draw_iter(refresh.as_pixels()).unwrap();
+    let flushed_display = sim_display.clone();
+    let display = Display::register(buffer, HOR_RES, VER_RES, move |refresh| {
+        flushed_display
+            .borrow_mut()
+            .draw_iter(refresh.as_pixels())
+            .unwrap();
     })?;
 
     let mut screen = display.get_scr_act()?;
@@ -86,7 +93,7 @@ fn main() -> Result<(), LvError> {
         i += 1;
 
         lvgl::task_handler();
-        window.update(&sim_display);
+        window.update(&sim_display.borrow());
 
         for event in window.events() {
             match event {
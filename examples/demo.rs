@@ -10,6 +10,8 @@ use lvgl::style::Style;
 use lvgl::widgets::Label;
 use lvgl::{Align, Color, Display, DrawBuffer, LvError, Part, TextAlign, Widget};
 use lvgl_sys;
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::thread::sleep;
 use std::time::Duration;
 use std::time::Instant;
@@ -18,8 +20,9 @@ fn main() -> Result<(), LvError> {
     const HOR_RES: u32 = 240;
     const VER_RES: u32 = 240;
 
-    let mut sim_display: SimulatorDisplay<Rgb565> =
-        SimulatorDisplay::new(Size::new(HOR_RES, VER_RES));
+    let sim_display = Rc::new(RefCell::new(SimulatorDisplay::<Rgb565>::new(Size::new(
+        HOR_RES, VER_RES,
+    ))));
     let output_settings = OutputSettingsBuilder::new().scale(1).build();
     let mut window = Window::new("PineTime", &output_settings);
 
@@ -29,8 +32,12 @@ fn main() -> Result<(), LvError> {
 
     // Register your display update callback with LVGL. The closure you pass here will be called
     // whenever LVGL has updates to be painted to the display.
-    let display = Display::register(buffer, HOR_RES, VER_RES, |refresh| {
-        sim_display.draw_iter(refresh.as_pixels()).unwrap();
+    let flushed_display = sim_display.clone();
+    let display = Display::register(buffer, HOR_RES, VER_RES, move |refresh| {
+        flushed_display
+            .borrow_mut()
+            .draw_iter(refresh.as_pixels())
+            .unwrap();
     });
 
     // Create screen and widgets
@@ -80,7 +87,7 @@ fn main() -> Result<(), LvError> {
         i = 1 + i;
 
         lvgl::task_handler();
-        window.update(&sim_display);
+        window.update(&sim_display.borrow());
 
         for event in window.events() {
             match event {
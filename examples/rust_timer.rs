@@ -8,6 +8,8 @@ use lvgl;
 use lvgl::style::Style;
 use lvgl::widgets::{Bar, Label};
 use lvgl::{Align, AnimationState, Color, Display, DrawBuffer, Event, LvError, Part, Widget};
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::thread::sleep;
 use std::time::Duration;
 use std::time::Instant;
@@ -36,16 +38,21 @@ fn main() -> Result<(), LvError> {
     const HOR_RES: u32 = 240;
     const VER_RES: u32 = 240;
 
-    let mut sim_display: SimulatorDisplay<Rgb565> =
-        SimulatorDisplay::new(Size::new(HOR_RES, VER_RES));
+    let sim_display = Rc::new(RefCell::new(SimulatorDisplay::<Rgb565>::new(Size::new(
+        HOR_RES, VER_RES,
+    ))));
 
     let output_settings = OutputSettingsBuilder::new().scale(2).build();
     let mut window = Window::new("Bar Example", &output_settings);
 
     let buffer = DrawBuffer::<{ (HOR_RES * VER_RES) as usize }>::default();
 
-    let display = Display::register(buffer, HOR_RES, VER_RES, |refresh| {
-        sim_display.draw_iter(refresh.as_pixels()).unwrap();
+    let flushed_display = sim_display.clone();
+    let display = Display::register(buffer, HOR_RES, VER_RES, move |refresh| {
+        flushed_display
+            .borrow_mut()
+            .draw_iter(refresh.as_pixels())
+            .unwrap();
     })?;
 
     let mut screen = display.get_scr_act()?;
@@ -60,7 +67,7 @@ fn main() -> Result<(), LvError> {
     bar.set_size(175, 20)?;
     bar.set_align(Align::Center, 0, 10)?;
     bar.set_range(0, 100)?;
-    bar.on_event(|_b, _e| {
+    bar.on_event(|_b, _e, _| {
         println!("Completed!");
     })?;
 
@@ -88,7 +95,7 @@ fn main() -> Result<(), LvError> {
         i += 1;
 
         lvgl::task_handler();
-        window.update(&mut sim_display);
+        window.update(&sim_display.borrow());
 
         for event in window.events() {
             match event {
@@ -37,9 +37,10 @@ fn main() -> LvResult<()> {
     btn_lbl.set_text(CString::new("Click me!").unwrap().as_c_str())?;
 
     let mut btn_state = false;
-    button.on_event(|_btn, event| {
+    button.on_event(move |btn, event, _| {
         println!("Button received event: {:?}", event);
         if let lvgl::Event::Clicked = event {
+            let mut btn_lbl: Label = btn.get_child(0).and_then(|lbl| lbl.downcast()).unwrap();
             if btn_state {
                 let nt = CString::new("Click me!").unwrap();
                 btn_lbl.set_text(nt.as_c_str()).unwrap();
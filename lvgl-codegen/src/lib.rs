@@ -27,6 +27,20 @@ lazy_static! {
     .collect();
 }
 
+lazy_static! {
+    /// The special event types of widgets, defined in `lvgl::widgets`. Other
+    /// widgets have no special events.
+    static ref SPECIAL_EVENTS: HashMap<&'static str, &'static str> = [
+        ("keyboard", "KeyboardEvent"),
+        ("textarea", "TextareaEvent"),
+        ("dropdown", "DropdownEvent"),
+        ("chart", "ChartEvent"),
+    ]
+    .iter()
+    .cloned()
+    .collect();
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum WrapperError {
     Skip,
@@ -63,8 +77,22 @@ impl Rusty for LvWidget {
 
         let widget_name = format_ident!("{}", self.pascal_name());
         let methods: Vec<TokenStream> = self.methods.iter().flat_map(|m| m.code(self)).collect();
+        let object = match SPECIAL_EVENTS.get(self.name.as_str()) {
+            Some(event) => {
+                let event = format_ident!("{}", event);
+                quote!(define_object!(#widget_name, event = crate::widgets::#event);)
+            }
+            None => quote!(define_object!(#widget_name);),
+        };
+        let class = format_ident!("{}{}_class", LIB_PREFIX, self.name.as_str());
         Ok(quote! {
-            define_object!(#widget_name);
+            #object
+
+            impl crate::WidgetClass for #widget_name<'_> {
+                fn class() -> &'static lvgl_sys::lv_obj_class_t {
+                    unsafe { &lvgl_sys::#class }
+                }
+            }
 
             impl<'a> #widget_name<'a> {
                 #(#methods)*
@@ -738,6 +766,12 @@ mod test {
         let expected_code = quote! {
             define_object!(Arc);
 
+            impl crate::WidgetClass for Arc<'_> {
+                fn class() -> &'static lvgl_sys::lv_obj_class_t {
+                    unsafe { &lvgl_sys::lv_arc_class }
+                }
+            }
+
             impl<'a> Arc<'a> {
 
             }
@@ -746,6 +780,31 @@ mod test {
         assert_eq!(code.to_string(), expected_code.to_string());
     }
 
+    #[test]
+    fn generate_widget_with_special_events_code() {
+        let keyboard_widget = LvWidget {
+            name: "keyboard".to_string(),
+            methods: vec![],
+        };
+
+        let code = keyboard_widget.code(&()).unwrap();
+        let expected_code = quote! {
+            define_object!(Keyboard, event = crate::widgets::KeyboardEvent);
+
+            impl crate::WidgetClass for Keyboard<'_> {
+                fn class() -> &'static lvgl_sys::lv_obj_class_t {
+                    unsafe { &lvgl_sys::lv_keyboard_class }
+                }
+            }
+
+            impl<'a> Keyboard<'a> {
+
+            }
+        };
+
+        assert_eq!(code.to_string(), expected_code.to_string());
+    }
+
     #[test]
     fn generate_widget_with_constructor_code() {
         // pub fn lv_arc_create(par: *mut lv_obj_t, copy: *const lv_obj_t) -> *mut lv_obj_t;
@@ -770,6 +829,12 @@ mod test {
         let expected_code = quote! {
             define_object!(Arc);
 
+            impl crate::WidgetClass for Arc<'_> {
+                fn class() -> &'static lvgl_sys::lv_obj_class_t {
+                    unsafe { &lvgl_sys::lv_arc_class }
+                }
+            }
+
             impl<'a> Arc<'a> {
                 pub fn create(parent: &mut impl crate::NativeObject) -> crate::LvResult<Self> {
                     unsafe {
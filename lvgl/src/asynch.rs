@@ -0,0 +1,356 @@
+//! Async support.
+//!
+//! Events of a widget can be awaited as a `Stream` instead of being handled in
+//! closures, and LVGL can be driven by an async task with `run`. Neither
+//! depends on a particular executor:
+//!
+//! ```ignore
+//! use futures::StreamExt;
+//! use lvgl::Widget;
+//!
+//! async fn ui_task(mut button: Btn<'static>) {
+//!     let mut events = button.events().unwrap();
+//!     while let Some(event) = events.next().await {
+//!         if event == Event::Clicked {
+//!             // ...
+//!         }
+//!     }
+//! }
+//!
+//! // Run LVGL next to the UI task, e.g. with embassy
+//! let clock = || Duration::from_micros(Instant::now().as_micros());
+//! let delay = |d: Duration| Timer::after_micros(d.as_micros() as u64);
+//! join(ui_task(button), lvgl::asynch::run(clock, delay)).await;
+//! ```
+
+use crate::{Box, Event, HandlerId, LvResult, NativeObject, SpecialEvent, Widget};
+use core::convert::Infallible;
+use core::future::Future;
+use core::mem::ManuallyDrop;
+use core::pin::Pin;
+use core::ptr::NonNull;
+use core::task::{Context, Poll, Waker};
+use core::time::Duration;
+use futures_core::Stream;
+
+/// The number of events buffered by the stream returned by `Widget::events`.
+pub const DEFAULT_EVENT_CAPACITY: usize = 16;
+
+/// The longest time `run` waits before calling the LVGL timer handler again.
+const MAX_DELAY: Duration = Duration::from_millis(50);
+
+/// The shortest time `run` waits, so that it yields to other tasks even if
+/// LVGL asks to be called again right away.
+const MIN_DELAY: Duration = Duration::from_millis(1);
+
+/// A fixed-size queue of events, filled by the event handler of a widget and
+/// drained by its stream.
+struct Channel<S, const N: usize> {
+    events: [Option<Event<S>>; N],
+    head: usize,
+    len: usize,
+    dropped: usize,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+impl<S, const N: usize> Channel<S, N> {
+    fn push(&mut self, event: Event<S>) {
+        if self.len < N {
+            self.events[(self.head + self.len) % N] = Some(event);
+            self.len += 1;
+        } else {
+            self.dropped = self.dropped.wrapping_add(1);
+        }
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn pop(&mut self) -> Option<Event<S>> {
+        if self.len == 0 {
+            return None;
+        }
+        let event = self.events[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        event
+    }
+}
+
+/// A stream of the events of a widget, returned by `Widget::events`.
+///
+/// Up to `N` events are buffered; newer events are dropped while the buffer
+/// is full, and counted by `dropped`. Events which are only used to draw and
+/// lay out the widget, such as `Event::DrawMain` or `Event::HitTest`, are not
+/// streamed. Custom events are streamed as `Event::Custom` without their
+/// parameter, which is only available to event handlers through
+/// `EventContext::param`. The stream ends after the `Delete` event of the
+/// widget. Dropping the stream removes its event handler from the widget.
+pub struct EventStream<S, const N: usize = DEFAULT_EVENT_CAPACITY> {
+    // Freed by LVGL if it is deinitialized first
+    channel: ManuallyDrop<Pin<Box<Channel<S, N>>>>,
+    obj: NonNull<lvgl_sys::lv_obj_t>,
+    handler: HandlerId,
+    generation: usize,
+}
+
+impl<S: SpecialEvent, const N: usize> EventStream<S, N> {
+    /// Subscribes to the events of a widget, buffering up to `N` of them.
+    pub fn new<'a, W>(widget: &mut W) -> LvResult<Self>
+    where
+        W: Widget<'a, SpecialEvent = S>,
+        S: 'static,
+    {
+        let mut channel = Box::pin(Channel {
+            events: core::array::from_fn(|_| None),
+            head: 0,
+            len: 0,
+            dropped: 0,
+            waker: None,
+            closed: false,
+        });
+        // The channel is pinned, and the handler is removed before it is
+        // dropped unless the widget was deleted first
+        let raw_channel: *mut Channel<S, N> = unsafe { channel.as_mut().get_unchecked_mut() };
+        let handler = widget.add_event_handler(None, move |_, event, _| {
+            let channel = unsafe { &mut *raw_channel };
+            if matches!(event, Event::Delete) {
+                channel.closed = true;
+            }
+            if !is_internal(&event) {
+                channel.push(event);
+            }
+        })?;
+        Ok(Self {
+            channel: ManuallyDrop::new(channel),
+            obj: widget.raw(),
+            handler,
+            generation: crate::generation(),
+        })
+    }
+}
+
+impl<S, const N: usize> EventStream<S, N> {
+    /// Returns the number of events dropped so far because the buffer was
+    /// full.
+    pub fn dropped(&self) -> usize {
+        self.channel.dropped
+    }
+}
+
+impl<S, const N: usize> Stream for EventStream<S, N> {
+    type Item = Event<S>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Safety: the channel is never moved out of its pin.
+        let channel = unsafe {
+            self.get_unchecked_mut()
+                .channel
+                .as_mut()
+                .get_unchecked_mut()
+        };
+        match channel.pop() {
+            Some(event) => Poll::Ready(Some(event)),
+            None if channel.closed => Poll::Ready(None),
+            None => {
+                channel.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.channel.len, None)
+    }
+}
+
+impl<S, const N: usize> Drop for EventStream<S, N> {
+    fn drop(&mut self) {
+        if self.generation != crate::generation() {
+            return;
+        }
+        // The handler was already freed if the widget was deleted
+        if !self.channel.closed {
+            let mut obj = unsafe { crate::Obj::from_raw(self.obj) }.unwrap();
+            let _ = obj.remove_event_handler(self.handler);
+        }
+        unsafe { ManuallyDrop::drop(&mut self.channel) }
+    }
+}
+
+fn is_internal<S>(event: &Event<S>) -> bool {
+    matches!(
+        event,
+        Event::HitTest
+            | Event::CoverCheck
+            | Event::RefrExtDrawSize
+            | Event::DrawMainBegin
+            | Event::DrawMain
+            | Event::DrawMainEnd
+            | Event::DrawPostBegin
+            | Event::DrawPost
+            | Event::DrawPostEnd
+            | Event::DrawPartBegin(_)
+            | Event::DrawPartEnd(_)
+            | Event::GetSelfSize
+    )
+}
+
+/// Runs LVGL forever, calling its timer handler and waiting as long as it
+/// asks for in between with `delay`, a function returning a future which
+/// completes after the given duration, e.g. `embassy_time::Timer::after` or
+/// `tokio::time::sleep`. Every iteration waits at least a millisecond, so the
+/// loop never keeps other tasks from running.
+///
+/// `clock` returns the current time of a monotonic clock, as the duration
+/// since any fixed point in time. Unless the `rust_timer` or `custom_timer`
+/// feature is enabled, the tick is advanced by the time which actually
+/// elapsed according to `clock`, however long the delays took. Otherwise,
+/// `clock` is not used.
+pub async fn run<C, F, D>(clock: C, mut delay: F) -> Infallible
+where
+    C: FnMut() -> Duration,
+    F: FnMut(Duration) -> D,
+    D: Future<Output = ()>,
+{
+    #[cfg(not(any(feature = "rust_timer", feature = "custom_timer")))]
+    let mut clock = clock;
+    #[cfg(not(any(feature = "rust_timer", feature = "custom_timer")))]
+    let mut last = clock();
+    #[cfg(any(feature = "rust_timer", feature = "custom_timer"))]
+    let _ = clock;
+    loop {
+        let next = unsafe { lvgl_sys::lv_timer_handler() };
+        let duration = Duration::from_millis(next as u64).clamp(MIN_DELAY, MAX_DELAY);
+        delay(duration).await;
+        #[cfg(not(any(feature = "rust_timer", feature = "custom_timer")))]
+        {
+            // Only whole milliseconds are passed on, the rest is carried over
+            let elapsed = Duration::from_millis(clock().saturating_sub(last).as_millis() as u64);
+            crate::tick_inc(elapsed);
+            last += elapsed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::event_send;
+    use crate::widgets::Btn;
+    use core::cell::Cell;
+    use core::future::pending;
+    use core::task::{RawWaker, RawWakerVTable};
+
+    fn waker(woken: &Cell<bool>) -> Waker {
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(
+            |data| RawWaker::new(data, &VTABLE),
+            |data| unsafe { (*(data as *const Cell<bool>)).set(true) },
+            |data| unsafe { (*(data as *const Cell<bool>)).set(true) },
+            |_| {},
+        );
+        let raw = RawWaker::new(woken as *const Cell<bool> as *const (), &VTABLE);
+        unsafe { Waker::from_raw(raw) }
+    }
+
+    #[test]
+    fn stream_widget_events() {
+        crate::tests::initialize_test(true);
+        let mut btn = Btn::new().unwrap();
+        let mut events = EventStream::<_, 2>::new(&mut btn).unwrap();
+        let woken = Cell::new(false);
+        let waker = waker(&woken);
+        let mut cx = Context::from_waker(&waker);
+
+        assert_eq!(Pin::new(&mut events).poll_next(&mut cx), Poll::Pending);
+        event_send(&mut btn, Event::Clicked).unwrap();
+        event_send(&mut btn, Event::DrawMain).unwrap();
+        event_send(&mut btn, Event::Released).unwrap();
+        event_send(&mut btn, Event::Pressed).unwrap();
+        assert!(woken.get());
+        assert_eq!(events.dropped(), 1);
+
+        let mut next = || Pin::new(&mut events).poll_next(&mut cx);
+        assert_eq!(next(), Poll::Ready(Some(Event::Clicked)));
+        assert_eq!(next(), Poll::Ready(Some(Event::Released)));
+        assert_eq!(next(), Poll::Pending);
+
+        unsafe { lvgl_sys::lv_obj_del(btn.raw().as_ptr()) };
+        assert_eq!(next(), Poll::Ready(Some(Event::Delete)));
+        assert_eq!(next(), Poll::Ready(None));
+    }
+
+    #[test]
+    fn drop_stream_removes_handler() {
+        crate::tests::initialize_test(true);
+        let mut btn = btn_with_dropped_stream();
+        event_send(&mut btn, Event::Clicked).unwrap();
+    }
+
+    #[test]
+    fn stream_after_deinit() {
+        crate::tests::initialize_test(true);
+        let mut btn = Btn::new().unwrap();
+        let events = btn.events().unwrap();
+        crate::tests::initialize_test(true);
+        // Does not remove the handler from the freed object or free the
+        // channel a second time
+        drop(events);
+    }
+
+    fn btn_with_dropped_stream() -> Btn<'static> {
+        let mut btn = Btn::new().unwrap();
+        let _events = btn.events().unwrap();
+        btn
+    }
+
+    #[test]
+    fn run_waits_for_timers() {
+        crate::tests::initialize_test(true);
+        let delays = Cell::new(0);
+        let mut run = Box::pin(run(
+            || Duration::ZERO,
+            |duration| {
+                assert!(duration >= MIN_DELAY && duration <= MAX_DELAY);
+                delays.set(delays.get() + 1);
+                pending()
+            },
+        ));
+        let woken = Cell::new(false);
+        let waker = waker(&woken);
+        let mut cx = Context::from_waker(&waker);
+        assert!(run.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(delays.get(), 1);
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "rust_timer", feature = "custom_timer")))]
+    fn run_advances_tick_by_elapsed_time() {
+        crate::tests::initialize_test(true);
+        // Every delay completes right away, but takes 2.5ms on the clock
+        let now = Cell::new(Duration::ZERO);
+        let delays = Cell::new(0);
+        let mut run = Box::pin(run(
+            || now.get(),
+            |_| {
+                now.set(now.get() + Duration::from_micros(2500));
+                delays.set(delays.get() + 1);
+                let last = delays.get() == 4;
+                async move {
+                    if last {
+                        pending::<()>().await
+                    }
+                }
+            },
+        ));
+        let woken = Cell::new(false);
+        let waker = waker(&woken);
+        let mut cx = Context::from_waker(&waker);
+        let start = unsafe { lvgl_sys::lv_tick_get() };
+        assert!(run.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(delays.get(), 4);
+        // Three delays completed, and the half milliseconds are carried over
+        assert_eq!(unsafe { lvgl_sys::lv_tick_elaps(start) }, 7);
+    }
+}
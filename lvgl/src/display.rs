@@ -1,15 +1,17 @@
 use crate::functions::CoreError;
-use crate::Screen;
 use crate::{disp_drv_register, disp_get_default, get_str_act, NativeObject};
 use crate::{Box, Color};
+use crate::{Obj, Screen, Widget};
 use core::convert::TryInto;
 #[cfg(feature = "nightly")]
 use core::error::Error;
+use core::ffi::c_void;
 use core::fmt;
-use core::mem::{ManuallyDrop, MaybeUninit};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
 use core::pin::Pin;
 use core::ptr::NonNull;
-use core::{ptr, result};
+use core::time::Duration;
+use core::{ptr, result, slice};
 
 /// Error in interacting with a `Display`.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -53,7 +55,9 @@ impl<'a> Display {
     }
 
     /// Registers a given `DrawBuffer` with an associated update function to
-    /// LVGL. `display_update` takes a `&DisplayRefresh`.
+    /// LVGL. `display_update` takes a `&DisplayRefresh`, and is kept by LVGL
+    /// for as long as the display exists, so it must not borrow anything. See
+    /// `DisplayBuilder` to set the other driver callbacks and options.
     pub fn register<F, const N: usize>(
         draw_buffer: DrawBuffer<N>,
         hor_res: u32,
@@ -61,14 +65,53 @@ impl<'a> Display {
         display_update: F,
     ) -> Result<Self>
     where
-        F: FnMut(&DisplayRefresh<N>) + 'a,
+        F: FnMut(&DisplayRefresh) + 'static,
     {
-        let mut display_diver = DisplayDriver::new(draw_buffer, display_update)?;
-        let disp_p = &mut display_diver.disp_drv;
-        disp_p.hor_res = hor_res.try_into().unwrap_or(240);
-        disp_p.ver_res = ver_res.try_into().unwrap_or(240);
-        Ok(disp_drv_register(&mut display_diver, None)?)
-        //display_diver.disp_drv.leak();
+        DisplayBuilder::new(draw_buffer, hor_res, ver_res)
+            .flush(display_update)
+            .register()
+    }
+
+    /// Registers a given `DrawBuffer` with an update function that completes
+    /// the flush asynchronously. `display_update` takes a `&DisplayRefresh`
+    /// and a `FlushToken`; LVGL will not reuse the flushed buffer until the
+    /// token is completed or dropped, so it can be moved into e.g. a DMA
+    /// transfer-complete interrupt. Combine with `DrawBuffer::double_buffered`
+    /// to let LVGL render into the second buffer in the meantime. Like with
+    /// `register`, `display_update` must not borrow anything.
+    pub fn register_async_flush<F, const N: usize>(
+        draw_buffer: DrawBuffer<N>,
+        hor_res: u32,
+        ver_res: u32,
+        display_update: F,
+    ) -> Result<Self>
+    where
+        F: FnMut(&DisplayRefresh, FlushToken) + 'static,
+    {
+        DisplayBuilder::new(draw_buffer, hor_res, ver_res)
+            .flush_async(display_update)
+            .register()
+    }
+
+    /// Returns an iterator over all registered displays, starting with the
+    /// first one registered.
+    pub fn all() -> Displays {
+        Displays {
+            next: unsafe { lvgl_sys::lv_disp_get_next(ptr::null_mut()) },
+        }
+    }
+
+    /// Creates a new screen on this display. Widgets created on the screen
+    /// are shown on this display once it is loaded with `set_scr_act`.
+    pub fn create_screen(&self) -> Result<Screen<'a>> {
+        unsafe {
+            let default = lvgl_sys::lv_disp_get_default();
+            // LVGL always creates screens on the default display.
+            lvgl_sys::lv_disp_set_default(self.disp.as_ptr());
+            let screen = Screen::blank();
+            lvgl_sys::lv_disp_set_default(default);
+            Ok(screen?)
+        }
     }
 
     /// Returns the current active screen.
@@ -82,6 +125,124 @@ impl<'a> Display {
         unsafe { lvgl_sys::lv_disp_load_scr(scr_ptr) }
     }
 
+    /// Makes this the default display, on which e.g. widgets created with
+    /// `new()` are placed.
+    pub fn set_default(&self) {
+        unsafe { lvgl_sys::lv_disp_set_default(self.disp.as_ptr()) }
+    }
+
+    /// Returns the horizontal resolution of the display, taking its rotation
+    /// into account.
+    pub fn hor_res(&self) -> u32 {
+        unsafe { lvgl_sys::lv_disp_get_hor_res(self.disp.as_ptr()) as u32 }
+    }
+
+    /// Returns the vertical resolution of the display, taking its rotation
+    /// into account.
+    pub fn ver_res(&self) -> u32 {
+        unsafe { lvgl_sys::lv_disp_get_ver_res(self.disp.as_ptr()) as u32 }
+    }
+
+    /// Changes the physical resolution of the display, e.g. after a different
+    /// panel has been connected. The `DrawBuffer` is kept; it must still be
+    /// large enough if full refresh or direct mode are used.
+    pub fn set_resolution(&mut self, hor_res: u32, ver_res: u32) -> Result<()> {
+        let hor_res = hor_res.try_into().map_err(|_| DisplayError::NotAvailable)?;
+        let ver_res = ver_res.try_into().map_err(|_| DisplayError::NotAvailable)?;
+        self.update_driver(|driver| {
+            driver.hor_res = hor_res;
+            driver.ver_res = ver_res;
+        })
+    }
+
+    /// Returns the dots per inch of the display.
+    pub fn dpi(&self) -> u32 {
+        unsafe { lvgl_sys::lv_disp_get_dpi(self.disp.as_ptr()) as u32 }
+    }
+
+    /// Sets the dots per inch of the display.
+    pub fn set_dpi(&mut self, dpi: u32) -> Result<()> {
+        self.update_driver(|driver| driver.set_dpi(dpi))
+    }
+
+    /// Rotates the display. Unless software rotation is enabled (see
+    /// `DisplayBuilder::sw_rotate`), LVGL renders with the horizontal and
+    /// vertical resolution swapped for 90 and 270 degrees, and the `Area` of
+    /// every `DisplayRefresh` is given in these rotated coordinates; the
+    /// display itself is then expected to rotate them. With software rotation,
+    /// the refreshed areas and pixels are already rotated to the physical
+    /// orientation of the display.
+    pub fn set_rotation(&mut self, rotation: DisplayRotation) {
+        unsafe { lvgl_sys::lv_disp_set_rotation(self.disp.as_ptr(), rotation.into()) }
+    }
+
+    /// Returns the current rotation of the display.
+    pub fn get_rotation(&self) -> DisplayRotation {
+        unsafe { lvgl_sys::lv_disp_get_rotation(self.disp.as_ptr()) }.into()
+    }
+
+    /// Modifies the driver of the display and lets LVGL apply the changes.
+    fn update_driver(&mut self, update: impl FnOnce(&mut lvgl_sys::lv_disp_drv_t)) -> Result<()> {
+        unsafe {
+            let driver = self
+                .disp
+                .as_ref()
+                .driver
+                .as_mut()
+                .ok_or(DisplayError::NotRegistered)?;
+            update(driver);
+            lvgl_sys::lv_disp_drv_update(self.disp.as_ptr(), driver);
+        }
+        Ok(())
+    }
+
+    /// Loads a `Screen` with an animation, after waiting for `delay`. If
+    /// `auto_delete` is set, the previously active screen is deleted once the
+    /// animation is done, and any handles to it or its children must no
+    /// longer be used.
+    ///
+    /// Fails if the screen was created on another display.
+    pub fn load_screen_anim(
+        &'a self,
+        screen: &'a mut Screen,
+        anim: ScrLoadAnim,
+        duration: Duration,
+        delay: Duration,
+        auto_delete: bool,
+    ) -> Result<()> {
+        if screen.display() != *self {
+            return Err(DisplayError::NotAvailable);
+        }
+        let duration = duration.as_millis().try_into().unwrap_or(u32::MAX);
+        let delay = delay.as_millis().try_into().unwrap_or(u32::MAX);
+        unsafe {
+            lvgl_sys::lv_scr_load_anim(
+                screen.raw().as_ptr(),
+                anim.into(),
+                duration,
+                delay,
+                auto_delete,
+            )
+        }
+        Ok(())
+    }
+
+    /// Returns the top layer of the display, which is drawn above every
+    /// screen. Useful for e.g. modal dialogs.
+    pub fn get_layer_top(&'a self) -> Result<Obj<'a>> {
+        let layer = unsafe { lvgl_sys::lv_disp_get_layer_top(self.disp.as_ptr()) };
+        unsafe { Obj::from_raw(NonNull::new(layer).ok_or(DisplayError::NotAvailable)?) }
+            .ok_or(DisplayError::NotAvailable)
+    }
+
+    /// Returns the system layer of the display, which is drawn above the top
+    /// layer. Useful for e.g. a mouse cursor.
+    pub fn get_layer_sys(&'a self) -> Result<Obj<'a>> {
+        let layer = unsafe { lvgl_sys::lv_disp_get_layer_sys(self.disp.as_ptr()) };
+        unsafe { Obj::from_raw(NonNull::new(layer).ok_or(DisplayError::NotAvailable)?) }
+            .ok_or(DisplayError::NotAvailable)
+    }
+
     /// Registers a display from raw functions and values.
     ///
     /// # Safety
@@ -141,6 +302,207 @@ impl<'a> Display {
     }
 }
 
+/// Builds and registers a `Display` with Rust closures for any of the
+/// callbacks of an `lv_disp_drv_t`. A flush callback, set with either `flush`
+/// or `flush_async`, is required; all others are optional.
+///
+/// ```no_run
+/// use lvgl::{DisplayBuilder, DrawBuffer};
+///
+/// lvgl::init();
+/// let buffer = DrawBuffer::<{ 128 * 8 }>::default();
+/// let display = DisplayBuilder::new(buffer, 128, 64)
+///     .flush(|_refresh| { /* send `refresh.colors` to the panel */ })
+///     // Monochrome panels are usually written to in pages of 8 rows
+///     .rounder(|area| {
+///         area.y1 &= !7;
+///         area.y2 |= 7;
+///     })
+///     .monitor(|_time_ms, _px| { /* record frame time */ })
+///     .antialiasing(false)
+///     .register()
+///     .unwrap();
+/// ```
+pub struct DisplayBuilder<const N: usize> {
+    driver: DisplayDriver<N>,
+    callbacks: DisplayCallbacks,
+}
+
+impl<const N: usize> DisplayBuilder<N> {
+    /// Creates a builder for a display of the given resolution, rendering
+    /// into `draw_buffer`.
+    pub fn new(draw_buffer: DrawBuffer<N>, hor_res: u32, ver_res: u32) -> Self {
+        let mut driver = DisplayDriver::new(draw_buffer);
+        driver.disp_drv.hor_res = hor_res.try_into().unwrap_or(240);
+        driver.disp_drv.ver_res = ver_res.try_into().unwrap_or(240);
+        Self {
+            driver,
+            callbacks: DisplayCallbacks::default(),
+        }
+    }
+
+    /// Sets the function writing rendered pixels to the display. LVGL is
+    /// notified that flushing is done as soon as it returns.
+    pub fn flush<F>(mut self, display_update: F) -> Self
+    where
+        F: FnMut(&DisplayRefresh) + 'static,
+    {
+        self.callbacks.flush = Some(Callback::new(display_update));
+        self.driver.disp_drv.flush_cb = Some(disp_flush_trampoline::<F>);
+        self
+    }
+
+    /// Sets the function writing rendered pixels to the display, completing
+    /// the flush through a `FlushToken`. See `Display::register_async_flush`.
+    pub fn flush_async<F>(mut self, display_update: F) -> Self
+    where
+        F: FnMut(&DisplayRefresh, FlushToken) + 'static,
+    {
+        self.callbacks.flush = Some(Callback::new(display_update));
+        self.driver.disp_drv.flush_cb = Some(disp_flush_async_trampoline::<F>);
+        self
+    }
+
+    /// Sets a function that can grow the area about to be redrawn, e.g. to
+    /// align it to the pages of a monochrome display.
+    pub fn rounder<F>(mut self, rounder: F) -> Self
+    where
+        F: FnMut(&mut Area) + 'static,
+    {
+        self.callbacks.rounder = Some(Callback::new(rounder));
+        self.driver.disp_drv.rounder_cb = Some(rounder_trampoline::<F>);
+        self
+    }
+
+    /// Sets a function writing a single pixel into the draw buffer, for
+    /// displays with a custom pixel format. It takes the draw buffer as
+    /// bytes, the width of the buffer in pixels, the coordinates and color of
+    /// the pixel, and its opacity (0 for transparent to 255 for opaque).
+    pub fn set_px<F>(mut self, set_px: F) -> Self
+    where
+        F: FnMut(&mut [u8], i16, i16, i16, Color, u8) + 'static,
+    {
+        self.callbacks.set_px = Some(Callback::new(set_px));
+        self.driver.disp_drv.set_px_cb = Some(set_px_trampoline::<F>);
+        self
+    }
+
+    /// Sets a function called after every refresh with the time it took in
+    /// milliseconds and the number of pixels refreshed.
+    pub fn monitor<F>(mut self, monitor: F) -> Self
+    where
+        F: FnMut(u32, u32) + 'static,
+    {
+        self.callbacks.monitor = Some(Callback::new(monitor));
+        self.driver.disp_drv.monitor_cb = Some(monitor_trampoline::<F>);
+        self
+    }
+
+    /// Sets a function called repeatedly while LVGL waits for a flush to
+    /// complete, e.g. to yield to other tasks.
+    pub fn wait<F>(mut self, wait: F) -> Self
+    where
+        F: FnMut() + 'static,
+    {
+        self.callbacks.wait = Some(Callback::new(wait));
+        self.driver.disp_drv.wait_cb = Some(wait_trampoline::<F>);
+        self
+    }
+
+    /// Sets a function cleaning the CPU data cache before the draw buffer is
+    /// read by e.g. a DMA controller.
+    pub fn clean_dcache<F>(mut self, clean_dcache: F) -> Self
+    where
+        F: FnMut() + 'static,
+    {
+        self.callbacks.clean_dcache = Some(Callback::new(clean_dcache));
+        self.driver.disp_drv.clean_dcache_cb = Some(clean_dcache_trampoline::<F>);
+        self
+    }
+
+    /// Sets a function called when LVGL starts rendering a frame.
+    pub fn render_start<F>(mut self, render_start: F) -> Self
+    where
+        F: FnMut() + 'static,
+    {
+        self.callbacks.render_start = Some(Callback::new(render_start));
+        self.driver.disp_drv.render_start_cb = Some(render_start_trampoline::<F>);
+        self
+    }
+
+    /// Always redraws the whole screen. `N` must then cover the entire
+    /// screen.
+    pub fn full_refresh(mut self, full_refresh: bool) -> Self {
+        self.driver.disp_drv.set_full_refresh(full_refresh as u32);
+        self
+    }
+
+    /// Renders directly into a screen-sized buffer, keeping pixels outside of
+    /// the refreshed area. `N` must then cover the entire screen, and the
+    /// `DisplayRefresh` rows are a whole screen width apart.
+    pub fn direct_mode(mut self, direct_mode: bool) -> Self {
+        self.driver.disp_drv.set_direct_mode(direct_mode as u32);
+        self
+    }
+
+    /// Enables or disables anti-aliasing.
+    pub fn antialiasing(mut self, antialiasing: bool) -> Self {
+        self.driver.disp_drv.set_antialiasing(antialiasing as u32);
+        self
+    }
+
+    /// Sets the dots per inch of the display.
+    pub fn dpi(mut self, dpi: u32) -> Self {
+        self.driver.disp_drv.set_dpi(dpi);
+        self
+    }
+
+    /// Lets LVGL rotate the rendered pixels in software if the display is
+    /// rotated.
+    pub fn sw_rotate(mut self, sw_rotate: bool) -> Self {
+        self.driver.disp_drv.set_sw_rotate(sw_rotate as u32);
+        self
+    }
+
+    /// Registers the display with LVGL. The closures are freed if this
+    /// fails.
+    pub fn register(self) -> Result<Display> {
+        let Self { driver, callbacks } = self;
+        if callbacks.flush.is_none() {
+            return Err(DisplayError::FailedToRegister);
+        }
+        // Neither the driver nor the callbacks can be dropped while LVGL uses
+        // them.
+        let mut driver = ManuallyDrop::new(driver);
+        let callbacks = Box::into_raw(Box::new(callbacks));
+        driver.disp_drv.user_data = callbacks as *mut _;
+        match disp_drv_register(&mut driver, None) {
+            Ok(display) => Ok(display),
+            Err(err) => {
+                unsafe {
+                    drop(Box::from_raw(callbacks));
+                    ManuallyDrop::drop(&mut driver);
+                }
+                Err(err.into())
+            }
+        }
+    }
+}
+
+impl PartialEq for Display {
+    fn eq(&self, other: &Self) -> bool {
+        self.disp == other.disp
+    }
+}
+
+impl Eq for Display {}
+
+impl fmt::Debug for Display {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Display").field(&self.disp).finish()
+    }
+}
+
 impl Default for Display {
     fn default() -> Self {
         disp_get_default().expect("LVGL must be INITIALIZED")
@@ -155,6 +517,21 @@ impl Drop for Display {
     }
 }
 
+/// An iterator over all registered displays. See `Display::all`.
+pub struct Displays {
+    next: *mut lvgl_sys::lv_disp_t,
+}
+
+impl Iterator for Displays {
+    type Item = Display;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let disp = NonNull::new(self.next)?;
+        self.next = unsafe { lvgl_sys::lv_disp_get_next(disp.as_ptr()) };
+        Some(Display::from_raw(disp, None))
+    }
+}
+
 /// Gets the active screen of the default display.
 pub(crate) fn get_scr_act() -> Result<Screen<'static>> {
     Ok(get_str_act(None)?.try_into()?)
@@ -163,32 +540,52 @@ pub(crate) fn get_scr_act() -> Result<Screen<'static>> {
 /// A buffer of size `N` representing `N` pixels. `N` can be smaller than the
 /// entire number of pixels on the screen, in which case the screen will be
 /// drawn to multiple times per frame.
+///
+/// A double-buffered `DrawBuffer` holds two such buffers, allowing LVGL to
+/// render into one while the other is being flushed.
 pub struct DrawBuffer<const N: usize> {
     draw_buf: Pin<Box<lvgl_sys::lv_disp_draw_buf_t>>,
     _refresh_buffer: Pin<Box<[MaybeUninit<lvgl_sys::lv_color_t>; N]>>,
+    _second_refresh_buffer: Option<Pin<Box<[MaybeUninit<lvgl_sys::lv_color_t>; N]>>>,
 }
 
 impl<const N: usize> Default for DrawBuffer<N> {
     fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl<const N: usize> DrawBuffer<N> {
+    /// Creates a `DrawBuffer` made of two buffers of `N` pixels each. This is
+    /// only useful together with `Display::register_async_flush`, as LVGL
+    /// otherwise waits for every flush to complete before rendering again.
+    pub fn double_buffered() -> Self {
+        Self::new(true)
+    }
+
+    fn new(double_buffered: bool) -> Self {
         let mut buf = Box::pin([MaybeUninit::uninit(); N]);
+        let mut second_buf = double_buffered.then(|| Box::pin([MaybeUninit::uninit(); N]));
         Self {
             draw_buf: Box::pin(unsafe {
                 let mut inner: MaybeUninit<lvgl_sys::lv_disp_draw_buf_t> = MaybeUninit::uninit();
                 let raw_ptr = buf.as_mut_ptr() as *mut _;
+                let second_raw_ptr = second_buf
+                    .as_mut()
+                    .map_or(ptr::null_mut(), |buf| buf.as_mut_ptr() as *mut _);
                 lvgl_sys::lv_disp_draw_buf_init(
                     inner.as_mut_ptr(),
                     raw_ptr,
-                    ptr::null_mut(),
+                    second_raw_ptr,
                     N as u32,
                 );
                 inner.assume_init()
             }),
             _refresh_buffer: buf,
+            _second_refresh_buffer: second_buf,
         }
     }
-}
 
-impl<const N: usize> DrawBuffer<N> {
     fn get_ptr(&mut self) -> &mut lvgl_sys::lv_disp_draw_buf_t {
         &mut self.draw_buf
     }
@@ -200,14 +597,8 @@ pub(crate) struct DisplayDriver<const N: usize> {
     _buffer: DrawBuffer<N>,
 }
 
-impl<'a, const N: usize> DisplayDriver<N> {
-    pub fn new<F>(
-        mut draw_buffer: DrawBuffer<N>,
-        display_update_callback: F,
-    ) -> Result<ManuallyDrop<Self>>
-    where
-        F: FnMut(&DisplayRefresh<N>) + 'a,
-    {
+impl<const N: usize> DisplayDriver<N> {
+    pub fn new(mut draw_buffer: DrawBuffer<N>) -> Self {
         let mut disp_drv = Box::pin(unsafe {
             let mut inner = MaybeUninit::uninit();
             lvgl_sys::lv_disp_drv_init(inner.as_mut_ptr());
@@ -217,17 +608,10 @@ impl<'a, const N: usize> DisplayDriver<N> {
         // Safety: The variable `draw_buffer` is statically allocated, no need to worry about this being dropped.
         disp_drv.draw_buf = draw_buffer.get_ptr() as *mut _;
 
-        disp_drv.user_data = Box::<F>::into_raw(Box::new(display_update_callback)) as *mut _;
-
-        // Sets trampoline pointer to the function implementation that uses the `F` type for a
-        // refresh buffer of size N specifically.
-        disp_drv.flush_cb = Some(disp_flush_trampoline::<F, N>);
-
-        // We do not store any memory that can be accidentally deallocated by on the Rust side.
-        Ok(ManuallyDrop::new(Self {
+        Self {
             disp_drv,
             _buffer: draw_buffer,
-        }))
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -288,6 +672,141 @@ impl<'a, const N: usize> DisplayDriver<N> {
     }
 }
 
+/// The rotation of a display.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DisplayRotation {
+    None,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl From<DisplayRotation> for lvgl_sys::lv_disp_rot_t {
+    fn from(rotation: DisplayRotation) -> Self {
+        match rotation {
+            DisplayRotation::None => lvgl_sys::lv_disp_rot_t_LV_DISP_ROT_NONE,
+            DisplayRotation::Rotate90 => lvgl_sys::lv_disp_rot_t_LV_DISP_ROT_90,
+            DisplayRotation::Rotate180 => lvgl_sys::lv_disp_rot_t_LV_DISP_ROT_180,
+            DisplayRotation::Rotate270 => lvgl_sys::lv_disp_rot_t_LV_DISP_ROT_270,
+        }
+    }
+}
+
+impl From<lvgl_sys::lv_disp_rot_t> for DisplayRotation {
+    fn from(rotation: lvgl_sys::lv_disp_rot_t) -> Self {
+        match rotation {
+            lvgl_sys::lv_disp_rot_t_LV_DISP_ROT_90 => DisplayRotation::Rotate90,
+            lvgl_sys::lv_disp_rot_t_LV_DISP_ROT_180 => DisplayRotation::Rotate180,
+            lvgl_sys::lv_disp_rot_t_LV_DISP_ROT_270 => DisplayRotation::Rotate270,
+            _ => DisplayRotation::None,
+        }
+    }
+}
+
+/// Transitions between screens. See `Display::load_screen_anim`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScrLoadAnim {
+    None,
+    OverLeft,
+    OverRight,
+    OverTop,
+    OverBottom,
+    MoveLeft,
+    MoveRight,
+    MoveTop,
+    MoveBottom,
+    FadeIn,
+    FadeOut,
+    OutLeft,
+    OutRight,
+    OutTop,
+    OutBottom,
+}
+
+impl From<ScrLoadAnim> for lvgl_sys::lv_scr_load_anim_t {
+    fn from(anim: ScrLoadAnim) -> Self {
+        match anim {
+            ScrLoadAnim::None => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_NONE,
+            ScrLoadAnim::OverLeft => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_OVER_LEFT,
+            ScrLoadAnim::OverRight => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_OVER_RIGHT,
+            ScrLoadAnim::OverTop => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_OVER_TOP,
+            ScrLoadAnim::OverBottom => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_OVER_BOTTOM,
+            ScrLoadAnim::MoveLeft => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_MOVE_LEFT,
+            ScrLoadAnim::MoveRight => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_MOVE_RIGHT,
+            ScrLoadAnim::MoveTop => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_MOVE_TOP,
+            ScrLoadAnim::MoveBottom => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_MOVE_BOTTOM,
+            ScrLoadAnim::FadeIn => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_FADE_IN,
+            ScrLoadAnim::FadeOut => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_FADE_OUT,
+            ScrLoadAnim::OutLeft => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_OUT_LEFT,
+            ScrLoadAnim::OutRight => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_OUT_RIGHT,
+            ScrLoadAnim::OutTop => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_OUT_TOP,
+            ScrLoadAnim::OutBottom => lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_OUT_BOTTOM,
+        }
+    }
+}
+
+/// A type-erased closure backing a callback of a display driver, freed when
+/// dropped.
+struct Callback {
+    closure: NonNull<c_void>,
+    free: unsafe fn(NonNull<c_void>),
+}
+
+impl Callback {
+    fn new<F>(closure: F) -> Self {
+        Self {
+            // Safety: `Box::into_raw` never returns a null pointer.
+            closure: unsafe { NonNull::new_unchecked(Box::into_raw(Box::new(closure)) as *mut _) },
+            free: free_callback::<F>,
+        }
+    }
+}
+
+impl Drop for Callback {
+    fn drop(&mut self) {
+        unsafe { (self.free)(self.closure) }
+    }
+}
+
+unsafe fn free_callback<F>(closure: NonNull<c_void>) {
+    drop(Box::from_raw(closure.as_ptr() as *mut F));
+}
+
+/// The closures backing the callbacks of a display driver. The `user_data`
+/// of drivers registered through a `DisplayBuilder` points to this table;
+/// each trampoline knows the concrete type of the closure it reads. The
+/// closures are freed with the table, i.e. when a builder is dropped or
+/// fails to register.
+#[derive(Default)]
+struct DisplayCallbacks {
+    flush: Option<Callback>,
+    rounder: Option<Callback>,
+    set_px: Option<Callback>,
+    monitor: Option<Callback>,
+    wait: Option<Callback>,
+    clean_dcache: Option<Callback>,
+    render_start: Option<Callback>,
+}
+
+impl DisplayCallbacks {
+    /// Returns the closure stored in the given slot of the table of
+    /// `disp_drv`.
+    ///
+    /// # Safety
+    ///
+    /// `disp_drv` must have been registered by a `DisplayBuilder`, and the
+    /// closure in `slot` must be of type `F`.
+    unsafe fn get<'b, F>(
+        disp_drv: *mut lvgl_sys::lv_disp_drv_t,
+        slot: fn(&Self) -> &Option<Callback>,
+    ) -> Option<&'b mut F> {
+        let callbacks = ((*disp_drv).user_data as *const Self).as_ref()?;
+        slot(callbacks)
+            .as_ref()
+            .map(|callback| &mut *(callback.closure.as_ptr() as *mut F))
+    }
+}
+
 /// Represents a sub-area of the display that is being updated.
 pub struct Area {
     pub x1: i16,
@@ -296,21 +815,112 @@ pub struct Area {
     pub y2: i16,
 }
 
+impl Area {
+    /// Returns the width of the area in pixels.
+    pub fn width(&self) -> usize {
+        (self.x2 - self.x1 + 1) as usize
+    }
+
+    /// Returns the height of the area in pixels.
+    pub fn height(&self) -> usize {
+        (self.y2 - self.y1 + 1) as usize
+    }
+}
+
+impl From<lvgl_sys::lv_area_t> for Area {
+    fn from(area: lvgl_sys::lv_area_t) -> Self {
+        Self {
+            x1: area.x1,
+            x2: area.x2,
+            y1: area.y1,
+            y2: area.y2,
+        }
+    }
+}
+
+impl From<Area> for lvgl_sys::lv_area_t {
+    fn from(area: Area) -> Self {
+        Self {
+            x1: area.x1,
+            y1: area.y1,
+            x2: area.x2,
+            y2: area.y2,
+        }
+    }
+}
+
 /// An update to the display information, contains the area that is being
 /// updated and the color of the pixels that need to be updated. The colors
-/// are represented in a contiguous array.
-pub struct DisplayRefresh<const N: usize> {
+/// are borrowed directly from LVGL's draw buffer and are laid out row by row,
+/// `stride` pixels apart. Unless the display is in direct mode, `stride` is
+/// the width of the area and `colors` holds exactly
+/// `area.width() * area.height()` pixels.
+///
+/// If the display is rotated without software rotation, `area` is given in
+/// rotated coordinates, i.e. with the horizontal and vertical resolution
+/// swapped for 90 and 270 degrees. See `Display::set_rotation`.
+pub struct DisplayRefresh<'a> {
     pub area: Area,
-    pub colors: [Color; N],
+    pub colors: &'a [Color],
+    pub stride: usize,
+    pub rotation: DisplayRotation,
+    pub sw_rotate: bool,
+}
+
+impl DisplayRefresh<'_> {
+    /// Returns the pixels being updated as native `lv_color_t` values. This
+    /// is the same memory as `colors`, and can be handed as-is to e.g. a DMA
+    /// transfer.
+    pub fn raw_colors(&self) -> &[lvgl_sys::lv_color_t] {
+        // Safety: `Color` is a transparent wrapper around `lv_color_t`.
+        unsafe {
+            slice::from_raw_parts(
+                self.colors.as_ptr() as *const lvgl_sys::lv_color_t,
+                self.colors.len(),
+            )
+        }
+    }
+}
+
+/// A pending flush of a `DisplayRefresh`, handed to the update function of a
+/// display registered with `Display::register_async_flush`. Completing or
+/// dropping the token tells LVGL that the flushed pixels have been sent to
+/// the display and their buffer can be reused.
+///
+/// Tokens can be sent to another task or an interrupt handler, but must not
+/// outlive the display they were created for.
+pub struct FlushToken {
+    disp_drv: NonNull<lvgl_sys::lv_disp_drv_t>,
+}
+
+impl FlushToken {
+    /// Returns true if this is the last flush of the current frame.
+    pub fn is_last(&self) -> bool {
+        unsafe { lvgl_sys::lv_disp_flush_is_last(self.disp_drv.as_ptr()) }
+    }
+
+    /// Signals to LVGL that flushing is complete. Equivalent to dropping the
+    /// token.
+    pub fn complete(self) {}
 }
 
+impl Drop for FlushToken {
+    fn drop(&mut self) {
+        unsafe { lvgl_sys::lv_disp_flush_ready(self.disp_drv.as_ptr()) }
+    }
+}
+
+// Safety: `lv_disp_flush_ready` only clears the flushing flags of the draw
+// buffer and is explicitly meant to be called from interrupts or other tasks.
+unsafe impl Send for FlushToken {}
+
 #[cfg(feature = "embedded_graphics")]
 mod embedded_graphics_impl {
     use crate::{Color, DisplayRefresh};
     use embedded_graphics::prelude::*;
     use embedded_graphics::Pixel;
 
-    impl<const N: usize> DisplayRefresh<N> {
+    impl DisplayRefresh<'_> {
         pub fn as_pixels<C>(&self) -> impl IntoIterator<Item = Pixel<C>> + '_
         where
             C: PixelColor + From<Color>,
@@ -323,13 +933,13 @@ mod embedded_graphics_impl {
 
             let ys = y1..=y2;
             let xs = (x1..=x2).enumerate();
-            let x_len = (x2 - x1 + 1) as usize;
+            let stride = self.stride;
 
             // We use iterators here to ensure that the Rust compiler can apply all possible
             // optimizations at compile time.
             ys.enumerate().flat_map(move |(iy, y)| {
                 xs.clone().map(move |(ix, x)| {
-                    let color_len = x_len * iy + ix;
+                    let color_len = stride * iy + ix;
                     let raw_color = self.colors[color_len];
                     Pixel(Point::new(x as i32, y as i32), raw_color.into())
                 })
@@ -338,33 +948,62 @@ mod embedded_graphics_impl {
     }
 }
 
-unsafe extern "C" fn disp_flush_trampoline<'a, F, const N: usize>(
+/// Wraps the area and pixels of a flush in a `DisplayRefresh`.
+///
+/// # Safety
+///
+/// The arguments must be the ones LVGL passed to `flush_cb`, and the result
+/// must not outlive the flush.
+unsafe fn display_refresh<'b>(
+    disp_drv: *mut lvgl_sys::lv_disp_drv_t,
+    area: *const lvgl_sys::lv_area_t,
+    color_p: *mut lvgl_sys::lv_color_t,
+) -> DisplayRefresh<'b> {
+    let area = Area::from(*area);
+    let driver = &*disp_drv;
+    let (offset, stride) = if driver.direct_mode() != 0 {
+        // The draw buffer covers the entire screen, as LVGL sees it after
+        // rotation, and `color_p` points to its start rather than to the
+        // start of the area.
+        let rotated = driver.rotated() as lvgl_sys::lv_disp_rot_t;
+        let stride = if rotated == lvgl_sys::lv_disp_rot_t_LV_DISP_ROT_90
+            || rotated == lvgl_sys::lv_disp_rot_t_LV_DISP_ROT_270
+        {
+            driver.ver_res
+        } else {
+            driver.hor_res
+        } as usize;
+        (area.y1 as usize * stride + area.x1 as usize, stride)
+    } else {
+        (0, area.width())
+    };
+    // Never reach past the end of the registered draw buffer, even if it is
+    // smaller than the area, e.g. with a misconfigured direct mode.
+    let size = (*driver.draw_buf).size as usize;
+    let offset = offset.min(size);
+    let len = ((area.height() - 1) * stride + area.width()).min(size - offset);
+    // Safety: LVGL renders one pixel per point of `area` into `color_p`, and
+    // `Color` is a transparent wrapper around `lv_color_t`.
+    let colors = slice::from_raw_parts(color_p.add(offset) as *const Color, len);
+    DisplayRefresh {
+        area,
+        colors,
+        stride,
+        rotation: ((*disp_drv).rotated() as lvgl_sys::lv_disp_rot_t).into(),
+        sw_rotate: (*disp_drv).sw_rotate() != 0,
+    }
+}
+
+unsafe extern "C" fn disp_flush_trampoline<'a, F>(
     disp_drv: *mut lvgl_sys::lv_disp_drv_t,
     area: *const lvgl_sys::lv_area_t,
     color_p: *mut lvgl_sys::lv_color_t,
 ) where
-    F: FnMut(&DisplayRefresh<N>) + 'a,
+    F: FnMut(&DisplayRefresh) + 'a,
 {
     let display_driver = *disp_drv;
-    if !display_driver.user_data.is_null() {
-        let callback = &mut *(display_driver.user_data as *mut F);
-
-        let mut colors = [Color::default(); N];
-        for (color_len, color) in colors.iter_mut().enumerate() {
-            let lv_color = *color_p.add(color_len);
-            *color = Color::from_raw(lv_color);
-        }
-
-        let update = DisplayRefresh {
-            area: Area {
-                x1: (*area).x1,
-                x2: (*area).x2,
-                y1: (*area).y1,
-                y2: (*area).y2,
-            },
-            colors,
-        };
-        callback(&update);
+    if let Some(callback) = DisplayCallbacks::get::<F>(disp_drv, |c| &c.flush) {
+        callback(&display_refresh(disp_drv, area, color_p));
     }
     // Not doing this causes a segfault in rust >= 1.69.0
     *disp_drv = display_driver;
@@ -372,6 +1011,99 @@ unsafe extern "C" fn disp_flush_trampoline<'a, F, const N: usize>(
     lvgl_sys::lv_disp_flush_ready(disp_drv);
 }
 
+unsafe extern "C" fn disp_flush_async_trampoline<'a, F>(
+    disp_drv: *mut lvgl_sys::lv_disp_drv_t,
+    area: *const lvgl_sys::lv_area_t,
+    color_p: *mut lvgl_sys::lv_color_t,
+) where
+    F: FnMut(&DisplayRefresh, FlushToken) + 'a,
+{
+    // If the callback is missing, dropping the token immediately marks the
+    // flush as ready.
+    let token = FlushToken {
+        disp_drv: NonNull::new_unchecked(disp_drv),
+    };
+    if let Some(callback) = DisplayCallbacks::get::<F>(disp_drv, |c| &c.flush) {
+        callback(&display_refresh(disp_drv, area, color_p), token);
+    }
+}
+
+unsafe extern "C" fn rounder_trampoline<'a, F>(
+    disp_drv: *mut lvgl_sys::lv_disp_drv_t,
+    area: *mut lvgl_sys::lv_area_t,
+) where
+    F: FnMut(&mut Area) + 'a,
+{
+    if let Some(callback) = DisplayCallbacks::get::<F>(disp_drv, |c| &c.rounder) {
+        let mut rounded = Area::from(*area);
+        callback(&mut rounded);
+        *area = rounded.into();
+    }
+}
+
+unsafe extern "C" fn set_px_trampoline<'a, F>(
+    disp_drv: *mut lvgl_sys::lv_disp_drv_t,
+    buf: *mut u8,
+    buf_w: lvgl_sys::lv_coord_t,
+    x: lvgl_sys::lv_coord_t,
+    y: lvgl_sys::lv_coord_t,
+    color: lvgl_sys::lv_color_t,
+    opa: lvgl_sys::lv_opa_t,
+) where
+    F: FnMut(&mut [u8], i16, i16, i16, Color, u8) + 'a,
+{
+    if let Some(callback) = DisplayCallbacks::get::<F>(disp_drv, |c| &c.set_px) {
+        // `buf` points into the active buffer of the registered draw buffer
+        let draw_buf = &*(*disp_drv).draw_buf;
+        let size = draw_buf.size as usize * mem::size_of::<lvgl_sys::lv_color_t>();
+        let offset = match (buf as usize).checked_sub(draw_buf.buf_act as usize) {
+            Some(offset) if offset <= size => offset,
+            _ => return,
+        };
+        let buf = slice::from_raw_parts_mut(buf, size - offset);
+        callback(buf, buf_w, x, y, Color::from_raw(color), opa);
+    }
+}
+
+unsafe extern "C" fn monitor_trampoline<'a, F>(
+    disp_drv: *mut lvgl_sys::lv_disp_drv_t,
+    time: u32,
+    px: u32,
+) where
+    F: FnMut(u32, u32) + 'a,
+{
+    if let Some(callback) = DisplayCallbacks::get::<F>(disp_drv, |c| &c.monitor) {
+        callback(time, px);
+    }
+}
+
+unsafe extern "C" fn wait_trampoline<'a, F>(disp_drv: *mut lvgl_sys::lv_disp_drv_t)
+where
+    F: FnMut() + 'a,
+{
+    if let Some(callback) = DisplayCallbacks::get::<F>(disp_drv, |c| &c.wait) {
+        callback();
+    }
+}
+
+unsafe extern "C" fn clean_dcache_trampoline<'a, F>(disp_drv: *mut lvgl_sys::lv_disp_drv_t)
+where
+    F: FnMut() + 'a,
+{
+    if let Some(callback) = DisplayCallbacks::get::<F>(disp_drv, |c| &c.clean_dcache) {
+        callback();
+    }
+}
+
+unsafe extern "C" fn render_start_trampoline<'a, F>(disp_drv: *mut lvgl_sys::lv_disp_drv_t)
+where
+    F: FnMut() + 'a,
+{
+    if let Some(callback) = DisplayCallbacks::get::<F>(disp_drv, |c| &c.render_start) {
+        callback();
+    }
+}
+
 impl From<CoreError> for DisplayError {
     fn from(err: CoreError) -> Self {
         use DisplayError::*;
@@ -386,6 +1118,8 @@ impl From<CoreError> for DisplayError {
 mod tests {
     use super::*;
     use crate::tests;
+    use core::cell::{Cell, RefCell};
+    use std::rc::Rc;
 
     #[test]
     fn get_scr_act_return_display() {
@@ -403,6 +1137,268 @@ mod tests {
         let _screen_default = get_scr_act().expect("Return screen from the default display");
     }
 
+    #[test]
+    fn flush_colors_cover_refreshed_area() {
+        tests::initialize_test(false);
+        const REFRESH_BUFFER_SIZE: usize = 240 * 240 / 10;
+        let buffer = DrawBuffer::<REFRESH_BUFFER_SIZE>::default();
+        let flushed = Rc::new(Cell::new(0));
+        let counter = flushed.clone();
+        let display = Display::register(buffer, 240, 240, move |refresh| {
+            assert_eq!(
+                refresh.colors.len(),
+                refresh.area.width() * refresh.area.height()
+            );
+            assert_eq!(refresh.raw_colors().len(), refresh.colors.len());
+            counter.set(counter.get() + refresh.colors.len());
+        })
+        .unwrap();
+        unsafe { lvgl_sys::lv_refr_now(display.disp.as_ptr()) };
+        assert_eq!(flushed.get(), 240 * 240);
+    }
+
+    #[test]
+    fn async_flush_with_double_buffer() {
+        tests::initialize_test(false);
+        const REFRESH_BUFFER_SIZE: usize = 240 * 240 / 10;
+        let buffer = DrawBuffer::<REFRESH_BUFFER_SIZE>::double_buffered();
+        assert!(!buffer.draw_buf.buf2.is_null());
+        let last_flushes = Rc::new(Cell::new(0));
+        let counter = last_flushes.clone();
+        let display = Display::register_async_flush(buffer, 240, 240, move |_, token| {
+            if token.is_last() {
+                counter.set(counter.get() + 1);
+            }
+            token.complete();
+        })
+        .unwrap();
+        unsafe { lvgl_sys::lv_refr_now(display.disp.as_ptr()) };
+        assert_eq!(last_flushes.get(), 1);
+    }
+
+    #[test]
+    fn builder_sets_callbacks_and_options() {
+        tests::initialize_test(false);
+        const REFRESH_BUFFER_SIZE: usize = 240 * 240 / 10;
+        let buffer = DrawBuffer::<REFRESH_BUFFER_SIZE>::default();
+        let rounded = Rc::new(Cell::new(false));
+        let monitored = Rc::new(Cell::new(0));
+        let (rounder_rounded, monitor_monitored) = (rounded.clone(), monitored.clone());
+        let display = DisplayBuilder::new(buffer, 240, 240)
+            .flush(|refresh| assert_eq!(refresh.area.y1 % 8, 0))
+            .rounder(move |area| {
+                area.y1 &= !7;
+                rounder_rounded.set(true);
+            })
+            .monitor(move |_, px| monitor_monitored.set(px))
+            .dpi(200)
+            .register()
+            .unwrap();
+        assert_eq!(
+            unsafe { lvgl_sys::lv_disp_get_dpi(display.disp.as_ptr()) },
+            200
+        );
+        unsafe { lvgl_sys::lv_refr_now(display.disp.as_ptr()) };
+        assert!(rounded.get());
+        assert!(monitored.get() > 0);
+    }
+
+    #[test]
+    fn builder_requires_flush() {
+        tests::initialize_test(false);
+        let buffer = DrawBuffer::<{ 240 * 240 / 10 }>::default();
+        assert!(matches!(
+            DisplayBuilder::new(buffer, 240, 240).register(),
+            Err(DisplayError::FailedToRegister)
+        ));
+    }
+
+    #[test]
+    fn builder_frees_closures() {
+        tests::initialize_test(false);
+        let captured = Rc::new(());
+        let rounder_captured = captured.clone();
+        let builder = DisplayBuilder::new(DrawBuffer::<{ 240 * 240 / 10 }>::default(), 240, 240)
+            .rounder(move |_| {
+                let _ = &rounder_captured;
+            });
+        assert_eq!(Rc::strong_count(&captured), 2);
+        drop(builder);
+        assert_eq!(Rc::strong_count(&captured), 1);
+
+        // Also when registering fails
+        let monitor_captured = captured.clone();
+        let builder = DisplayBuilder::new(DrawBuffer::<{ 240 * 240 / 10 }>::default(), 240, 240)
+            .monitor(move |_, _| {
+                let _ = &monitor_captured;
+            });
+        assert!(builder.register().is_err());
+        assert_eq!(Rc::strong_count(&captured), 1);
+    }
+
+    #[test]
+    fn direct_mode_flushes_area_of_screen_buffer() {
+        tests::initialize_test(false);
+        let flushes = Rc::new(RefCell::new(Vec::new()));
+        let flushed = flushes.clone();
+        let display = DisplayBuilder::new(DrawBuffer::<{ 20 * 10 }>::default(), 20, 10)
+            .flush(move |refresh| {
+                flushed.borrow_mut().push((
+                    refresh.area.x1,
+                    refresh.area.y1,
+                    refresh.stride,
+                    refresh.colors.as_ptr(),
+                    refresh.colors.len(),
+                ))
+            })
+            .direct_mode(true)
+            .register()
+            .unwrap();
+        unsafe { lvgl_sys::lv_refr_now(display.disp.as_ptr()) };
+        let (_, _, stride, start, len) = flushes.borrow()[0];
+        assert_eq!((stride, len), (20, 20 * 10));
+
+        flushes.borrow_mut().clear();
+        let area = lvgl_sys::lv_area_t {
+            x1: 5,
+            y1: 2,
+            x2: 8,
+            y2: 4,
+        };
+        unsafe {
+            let screen = lvgl_sys::lv_disp_get_scr_act(display.disp.as_ptr());
+            lvgl_sys::lv_obj_invalidate_area(screen, &area);
+            lvgl_sys::lv_refr_now(display.disp.as_ptr());
+        }
+        // The pixels of the area start 2 rows and 5 pixels into the buffer
+        let offset = 2 * 20 + 5;
+        assert_eq!(
+            flushes.borrow()[0],
+            (5, 2, 20, unsafe { start.add(offset) }, 2 * 20 + 4)
+        );
+    }
+
+    #[test]
+    fn set_px_gets_draw_buffer() {
+        tests::initialize_test(false);
+        const REFRESH_BUFFER_SIZE: usize = 240 * 240 / 10;
+        let length = Rc::new(Cell::new(0));
+        let set_px_length = length.clone();
+        let display = DisplayBuilder::new(DrawBuffer::<REFRESH_BUFFER_SIZE>::default(), 240, 240)
+            .flush(|_| {})
+            .set_px(move |buf, _, _, _, _, _| set_px_length.set(buf.len()))
+            .register()
+            .unwrap();
+        unsafe { lvgl_sys::lv_refr_now(display.disp.as_ptr()) };
+        // LVGL hands the start of the active buffer to `set_px`
+        assert_eq!(
+            length.get(),
+            REFRESH_BUFFER_SIZE * mem::size_of::<lvgl_sys::lv_color_t>()
+        );
+    }
+
+    #[test]
+    fn rotate_display() {
+        tests::initialize_test(false);
+        const REFRESH_BUFFER_SIZE: usize = 240 * 320 / 10;
+        let buffer = DrawBuffer::<REFRESH_BUFFER_SIZE>::default();
+        let rotated_within_bounds = Rc::new(Cell::new(true));
+        let within_bounds = rotated_within_bounds.clone();
+        let mut display = Display::register(buffer, 240, 320, move |refresh| {
+            if refresh.rotation == DisplayRotation::Rotate90 {
+                let area = &refresh.area;
+                within_bounds.set(within_bounds.get() && area.x2 < 320 && area.y2 < 240);
+            }
+        })
+        .unwrap();
+
+        display.set_rotation(DisplayRotation::Rotate90);
+        assert_eq!(display.get_rotation(), DisplayRotation::Rotate90);
+        assert_eq!((display.hor_res(), display.ver_res()), (320, 240));
+        unsafe { lvgl_sys::lv_refr_now(display.disp.as_ptr()) };
+        assert!(rotated_within_bounds.get());
+
+        display.set_rotation(DisplayRotation::None);
+        assert_eq!((display.hor_res(), display.ver_res()), (240, 320));
+    }
+
+    #[test]
+    fn change_resolution_and_dpi() {
+        tests::initialize_test(false);
+        const REFRESH_BUFFER_SIZE: usize = 240 * 240 / 10;
+        let buffer = DrawBuffer::<REFRESH_BUFFER_SIZE>::default();
+        let mut display = Display::register(buffer, 240, 240, |_| {}).unwrap();
+        display.set_resolution(320, 240).unwrap();
+        assert_eq!((display.hor_res(), display.ver_res()), (320, 240));
+        display.set_dpi(200).unwrap();
+        assert_eq!(display.dpi(), 200);
+    }
+
+    #[test]
+    fn set_default_display() {
+        tests::initialize_test(true);
+        const REFRESH_BUFFER_SIZE: usize = 240 * 240 / 10;
+        let buffer = DrawBuffer::<REFRESH_BUFFER_SIZE>::default();
+        let display = Display::register(buffer, 320, 240, |_| {}).unwrap();
+        assert_eq!(Display::default().hor_res(), 240);
+        display.set_default();
+        assert_eq!(Display::default().hor_res(), 320);
+    }
+
+    #[test]
+    fn multiple_displays() {
+        tests::initialize_test(true);
+        let main = Display::default();
+        const REFRESH_BUFFER_SIZE: usize = 128 * 64;
+        let buffer = DrawBuffer::<REFRESH_BUFFER_SIZE>::default();
+        let status = Display::register(buffer, 128, 64, |_| {}).unwrap();
+        let displays: Vec<Display> = Display::all().collect();
+        assert_eq!(
+            displays,
+            [Display::default(), Display::from_raw(status.disp, None)]
+        );
+
+        let mut screen = status.create_screen().unwrap();
+        assert_eq!(screen.display(), status);
+        assert_eq!(Display::default(), main);
+        assert_eq!(main.get_scr_act().unwrap().display(), main);
+        status.set_scr_act(&mut screen);
+        assert_eq!(status.get_scr_act().unwrap().raw(), screen.raw());
+    }
+
+    #[test]
+    fn load_screen_with_animation() {
+        tests::initialize_test(true);
+        let display = Display::default();
+        let mut screen = display.create_screen().unwrap();
+        display
+            .load_screen_anim(
+                &mut screen,
+                ScrLoadAnim::MoveLeft,
+                Duration::from_millis(100),
+                Duration::ZERO,
+                false,
+            )
+            .unwrap();
+
+        // Screens of other displays are not loaded
+        let buffer = DrawBuffer::<{ 240 * 240 / 10 }>::default();
+        let other = Display::register(buffer, 240, 240, |_| {}).unwrap();
+        let mut other_screen = other.create_screen().unwrap();
+        assert_eq!(
+            display.load_screen_anim(
+                &mut other_screen,
+                ScrLoadAnim::None,
+                Duration::ZERO,
+                Duration::ZERO,
+                false,
+            ),
+            Err(DisplayError::NotAvailable)
+        );
+        let _top = display.get_layer_top().unwrap();
+        let _sys = display.get_layer_sys().unwrap();
+    }
+
     #[test]
     fn register_display_directly() -> Result<()> {
         crate::tests::initialize_test(true);
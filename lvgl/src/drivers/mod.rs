@@ -45,8 +45,8 @@
 //!
 //! Input drivers can immediately be instantiated with no parameters. Similar
 //! to display drivers, initialization logic and registration with LVGL is
-//! performed automatically on macro invocation. Setting a custom `feedback_cb`
-//! is not (yet) supported, but can be done unsafely with raw C FFI calls.
+//! performed automatically on macro invocation. A Rust closure can be used as
+//! the `feedback_cb` of the device with `InputDriver::set_feedback`.
 //!
 //! Note: The `InputDevice` trait must be in scope at macro invocation.
 //!
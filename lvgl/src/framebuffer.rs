@@ -0,0 +1,317 @@
+//! A headless display rendering into memory.
+//!
+//! `FramebufferDisplay` does not need a windowing system or any hardware,
+//! which makes it useful for running tests and taking screenshots in CI:
+//!
+//! ```no_run
+//! use lvgl::framebuffer::FramebufferDisplay;
+//! use lvgl::DrawBuffer;
+//!
+//! lvgl::init();
+//! let buffer = DrawBuffer::<{ 320 * 24 }>::default();
+//! let display = FramebufferDisplay::register(buffer, 320, 240).unwrap();
+//! // ... create some widgets ...
+//! display.refresh();
+//! let screenshot = display.to_png();
+//! ```
+
+use crate::{
+    Color, Display, DisplayBuilder, DisplayError, DisplayRefresh, DisplayRotation, DrawBuffer,
+};
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::mem;
+use core::result;
+
+type Result<T> = result::Result<T, DisplayError>;
+
+/// A display that accumulates every flushed area into an in-memory frame,
+/// which can be queried pixel by pixel or exported as a PPM or PNG image.
+///
+/// Rotations are applied in software, so the frame always has the physical
+/// resolution of the display.
+pub struct FramebufferDisplay {
+    display: Display,
+    frame: Rc<RefCell<Frame>>,
+}
+
+impl FramebufferDisplay {
+    /// Registers a headless display of the given resolution with LVGL.
+    /// Fails for resolutions LVGL cannot represent as an `lv_coord_t`.
+    pub fn register<const N: usize>(
+        draw_buffer: DrawBuffer<N>,
+        hor_res: u32,
+        ver_res: u32,
+    ) -> Result<Self> {
+        // DisplayBuilder would fall back to a default resolution instead
+        if lvgl_sys::lv_coord_t::try_from(hor_res).is_err()
+            || lvgl_sys::lv_coord_t::try_from(ver_res).is_err()
+        {
+            return Err(DisplayError::FailedToRegister);
+        }
+        let frame = Frame::new(hor_res, ver_res).ok_or(DisplayError::FailedToRegister)?;
+        let frame = Rc::new(RefCell::new(frame));
+        let flushed_frame = frame.clone();
+        let display = DisplayBuilder::new(draw_buffer, hor_res, ver_res)
+            .flush(move |refresh| flushed_frame.borrow_mut().draw(refresh))
+            .sw_rotate(true)
+            .register()?;
+        Ok(Self { display, frame })
+    }
+
+    /// Returns the underlying `Display`.
+    pub fn display(&self) -> &Display {
+        &self.display
+    }
+
+    /// Renders everything that changed on the display into the frame.
+    pub fn refresh(&self) {
+        unsafe { lvgl_sys::lv_refr_now(self.display.disp.as_ptr()) }
+    }
+
+    /// Changes the resolution of the display. The frame is cleared and
+    /// everything will be redrawn on the next refresh.
+    pub fn set_resolution(&mut self, hor_res: u32, ver_res: u32) -> Result<()> {
+        let (width, height) = (self.width(), self.height());
+        // The frame is only replaced once the display accepted the resolution
+        self.display.set_resolution(hor_res, ver_res)?;
+        match Frame::new(hor_res, ver_res) {
+            Some(frame) => {
+                *self.frame.borrow_mut() = frame;
+                Ok(())
+            }
+            None => {
+                self.display.set_resolution(width, height)?;
+                Err(DisplayError::NotAvailable)
+            }
+        }
+    }
+
+    /// Rotates the display. See `Display::set_rotation`.
+    pub fn set_rotation(&mut self, rotation: DisplayRotation) {
+        self.display.set_rotation(rotation)
+    }
+
+    /// Returns the width of the frame in pixels.
+    pub fn width(&self) -> u32 {
+        self.frame.borrow().width
+    }
+
+    /// Returns the height of the frame in pixels.
+    pub fn height(&self) -> u32 {
+        self.frame.borrow().height
+    }
+
+    /// Returns the color of the pixel at the given coordinates, or `None` if
+    /// they are outside of the frame.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        let frame = self.frame.borrow();
+        if x < frame.width && y < frame.height {
+            Some(frame.pixels[y as usize * frame.width as usize + x as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the frame as 8-bit RGB triplets, row by row.
+    pub fn to_rgb(&self) -> Vec<u8> {
+        self.frame
+            .borrow()
+            .pixels
+            .iter()
+            .flat_map(|color| {
+                let (r, g, b) = color.to_rgb();
+                [r, g, b]
+            })
+            .collect()
+    }
+
+    /// Exports the frame as a binary PPM (P6) image.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        encode_ppm(self.width(), self.height(), &self.to_rgb())
+    }
+
+    /// Exports the frame as an uncompressed PNG image. PNG cannot represent
+    /// empty frames, for which this returns no data.
+    pub fn to_png(&self) -> Vec<u8> {
+        encode_png(self.width(), self.height(), &self.to_rgb())
+    }
+}
+
+struct Frame {
+    width: u32,
+    height: u32,
+    pixels: Vec<Color>,
+}
+
+impl Frame {
+    /// Creates a frame, or returns `None` if it would not fit in memory.
+    fn new(width: u32, height: u32) -> Option<Self> {
+        let len = (width as usize).checked_mul(height as usize)?;
+        len.checked_mul(mem::size_of::<Color>())?;
+        Some(Self {
+            width,
+            height,
+            pixels: vec![Color::default(); len],
+        })
+    }
+
+    fn draw(&mut self, refresh: &DisplayRefresh) {
+        let area = &refresh.area;
+        for (row, y) in (area.y1..=area.y2).enumerate() {
+            if y < 0 || y as u32 >= self.height {
+                continue;
+            }
+            for (column, x) in (area.x1..=area.x2).enumerate() {
+                if x < 0 || x as u32 >= self.width {
+                    continue;
+                }
+                self.pixels[y as usize * self.width as usize + x as usize] =
+                    refresh.colors[row * refresh.stride + column];
+            }
+        }
+    }
+}
+
+/// Encodes 8-bit RGB pixels as a binary PPM image.
+pub(crate) fn encode_ppm(width: u32, height: u32, rgb: &[u8]) -> Vec<u8> {
+    let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    ppm.extend_from_slice(rgb);
+    ppm
+}
+
+/// Encodes 8-bit RGB pixels as a PNG image, using uncompressed deflate
+/// blocks. Returns no data for empty images, which PNG cannot represent.
+pub(crate) fn encode_png(width: u32, height: u32, rgb: &[u8]) -> Vec<u8> {
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    // Every scanline starts with its filter type, which is always none.
+    let mut scanlines = Vec::with_capacity(rgb.len() + height as usize);
+    for row in rgb.chunks(width as usize * 3) {
+        scanlines.push(0);
+        scanlines.extend_from_slice(row);
+    }
+
+    // zlib header for deflate with no compression
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = scanlines.chunks(u16::MAX as usize).peekable();
+    while let Some(block) = blocks.next() {
+        zlib.push(blocks.peek().is_none() as u8);
+        zlib.extend((block.len() as u16).to_le_bytes());
+        zlib.extend((!(block.len() as u16)).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend(adler32(&scanlines).to_be_bytes());
+
+    let mut header = Vec::with_capacity(13);
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    // 8 bits per channel, truecolor, default compression, filtering and no
+    // interlacing
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    write_png_chunk(&mut png, b"IHDR", &header);
+    write_png_chunk(&mut png, b"IDAT", &zlib);
+    write_png_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |mut crc, &byte| {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+        crc
+    })
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::set_screen_color;
+
+    #[test]
+    fn render_into_frame() {
+        crate::tests::initialize_test(false);
+        let buffer = DrawBuffer::<{ 64 * 8 }>::default();
+        let display = FramebufferDisplay::register(buffer, 64, 48).unwrap();
+        set_screen_color(&display, (255, 0, 0));
+        display.refresh();
+
+        assert_eq!((display.width(), display.height()), (64, 48));
+        assert_eq!(display.pixel(63, 47).unwrap().to_rgb(), (255, 0, 0));
+        assert!(display.pixel(64, 0).is_none());
+        assert_eq!(display.to_rgb().len(), 64 * 48 * 3);
+    }
+
+    #[test]
+    fn reject_oversized_resolutions() {
+        crate::tests::initialize_test(false);
+        let too_wide = FramebufferDisplay::register(DrawBuffer::<{ 64 * 8 }>::default(), 40000, 48);
+        assert_eq!(too_wide.err(), Some(DisplayError::FailedToRegister));
+
+        let buffer = DrawBuffer::<{ 64 * 8 }>::default();
+        let mut display = FramebufferDisplay::register(buffer, 64, 48).unwrap();
+        assert!(display.set_resolution(64, 40000).is_err());
+        assert_eq!((display.width(), display.height()), (64, 48));
+        display.set_resolution(32, 16).unwrap();
+        assert_eq!((display.width(), display.height()), (32, 16));
+    }
+
+    #[test]
+    fn export_images() {
+        crate::tests::initialize_test(false);
+        let buffer = DrawBuffer::<{ 16 * 16 }>::default();
+        let display = FramebufferDisplay::register(buffer, 16, 8).unwrap();
+        display.refresh();
+
+        let ppm = display.to_ppm();
+        assert!(ppm.starts_with(b"P6\n16 8\n255\n"));
+        assert_eq!(ppm.len(), 12 + 16 * 8 * 3);
+
+        let png = display.to_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x10\0\0\0\x08"));
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+    }
+
+    #[test]
+    fn empty_and_oversized_frames() {
+        assert!(encode_png(0, 8, &[]).is_empty());
+        assert!(encode_png(16, 0, &[]).is_empty());
+        assert_eq!(Frame::new(0, 0).unwrap().pixels.len(), 0);
+        assert!(Frame::new(u32::MAX, u32::MAX).is_none());
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+}
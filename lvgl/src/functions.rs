@@ -1,6 +1,6 @@
 use crate::display::{Display, DisplayDriver};
-use crate::input_device::InputDriver;
-use crate::{Event, LvError, LvResult, Obj, Widget};
+use crate::{CustomEventId, Event, LvError, LvResult, Obj, SpecialEvent, Widget};
+use core::ffi::c_void;
 use core::ptr::NonNull;
 #[cfg(not(feature = "rust_timer"))]
 use core::time::Duration;
@@ -67,25 +67,44 @@ pub fn task_handler() {
     unsafe { lvgl_sys::lv_timer_handler() };
 }
 
-/// Directly send an event to a specific widget.
+/// Directly send an event to a specific widget. The key of `Event::Key` and
+/// the parameter of special events, such as the index of
+/// `ChartEvent::PointPressed`, are sent along as the event parameter. Fails
+/// for draw part events, which only LVGL can send, and for `Event::Gesture`,
+/// whose direction LVGL reads from the input device being processed.
+/// Gestures of a `MultiTouch` device are sent with `event_send_with_param`.
 #[inline]
-pub fn event_send<W: for<'a> Widget<'a>>(
+pub fn event_send<'a, W: Widget<'a>>(
     obj: &mut W,
-    event: Event<<W as Widget<'_>>::SpecialEvent>,
-) {
+    event: Event<<W as Widget<'a>>::SpecialEvent>,
+) -> LvResult<()> {
+    let mut param = match &event {
+        Event::Key(data) => Some(u32::from(*data)),
+        Event::Special(special) => special.param(),
+        Event::Gesture(_) | Event::DrawPartBegin(_) | Event::DrawPartEnd(_) => {
+            return Err(LvError::InvalidReference)
+        }
+        _ => None,
+    };
+    let code = event.into();
+    let param = param
+        .as_mut()
+        .map_or(ptr::null_mut(), |param| param as *mut u32 as *mut c_void);
     unsafe {
-        lvgl_sys::lv_event_send(obj.raw().as_mut(), event.into(), ptr::null_mut());
+        lvgl_sys::lv_event_send(obj.raw().as_mut(), code, param);
     };
+    Ok(())
 }
 
-/// Register an input device driver to LVGL.
-pub(crate) fn indev_drv_register<D>(input_device: &mut impl InputDriver<D>) -> LvResult<()> {
-    unsafe {
-        let descr = lvgl_sys::lv_indev_drv_register(input_device.get_driver() as *mut _);
-        if descr.is_null() {
-            return Err(LvError::LvOOMemory);
-        }
-        input_device.set_descriptor(descr)?;
-    };
+/// Sends a custom event to a specific widget, with a reference to `param`
+/// which its handlers can read with `EventContext::param::<P>()` while the
+/// event is being sent.
+#[inline]
+pub fn event_send_with_param<'a, W: Widget<'a>, P: 'static>(
+    obj: &mut W,
+    id: CustomEventId,
+    param: &P,
+) -> LvResult<()> {
+    unsafe { crate::event::send_custom_event(obj.raw().as_ptr(), id, param) };
     Ok(())
 }
@@ -0,0 +1,201 @@
+use super::generic::InputDevice;
+use super::{BufferStatus, Data, InputDriver, InputState};
+use crate::Box;
+use crate::Event;
+use crate::Point;
+use crate::{LvError, LvResult};
+use core::convert::Infallible;
+use core::pin::Pin;
+
+/// Button-specific input data. Contains the id of the button, which is the
+/// index of the point it clicks in the points of its `Button` device.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub struct ButtonInputData(pub u32);
+
+impl ButtonInputData {
+    pub fn pressed(self) -> InputState {
+        InputState::Pressed(Data::Button(self))
+    }
+
+    pub fn released(self) -> InputState {
+        InputState::Released(Data::Button(self))
+    }
+}
+
+/// Represents a button-type input driver, made of `N` hardware buttons that
+/// each click a fixed point on the screen. Reading the id of a button out of
+/// `0..N` is a read error, which disables the device.
+pub struct Button<const N: usize> {
+    device: InputDevice,
+    // LVGL keeps a reference to the points, so they must not move
+    points: Pin<Box<[lvgl_sys::lv_point_t; N]>>,
+}
+
+impl<const N: usize> Button<N> {
+    /// Registers a button input device clicking the given points. See
+    /// `InputDriver::register`.
+    pub fn register_with_points<F>(
+        points: [Point; N],
+        handler: F,
+        display: &crate::Display,
+    ) -> LvResult<Self>
+    where
+        F: FnMut() -> BufferStatus + 'static,
+    {
+        let mut dev = Self::register(handler, display)?;
+        dev.set_points(points)?;
+        Ok(dev)
+    }
+
+    /// Sets the point clicked by each button, indexed by button id.
+    pub fn set_points(&mut self, points: [Point; N]) -> LvResult<()> {
+        for (raw, point) in self.points.iter_mut().zip(points) {
+            raw.x = point.x as lvgl_sys::lv_coord_t;
+            raw.y = point.y as lvgl_sys::lv_coord_t;
+        }
+        let descriptor = self.device.descriptor().ok_or(LvError::InvalidReference)?;
+        // Without points, LVGL ignores the device instead of reading the
+        // point of button 0
+        if N > 0 {
+            unsafe { lvgl_sys::lv_indev_set_button_points(descriptor, self.points.as_ptr()) };
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> InputDriver<Button<N>> for Button<N> {
+    fn register_fallible<F, E>(mut handler: F, _: &crate::Display) -> LvResult<Button<N>>
+    where
+        F: FnMut() -> Result<BufferStatus, E> + 'static,
+    {
+        // LVGL reads the point of the button without checking its id, so
+        // fail reads of unknown buttons, with `None` as the error
+        let read = move || -> Result<BufferStatus, Option<E>> {
+            let status = handler().map_err(Some)?;
+            let (BufferStatus::Once(state) | BufferStatus::Buffered(state)) = status;
+            match state {
+                InputState::Pressed(Data::Button(ButtonInputData(id)))
+                | InputState::Released(Data::Button(ButtonInputData(id)))
+                    if id as usize >= N =>
+                {
+                    Err(None)
+                }
+                _ => Ok(status),
+            }
+        };
+        Ok(Self {
+            device: InputDevice::register(lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_BUTTON, read)?,
+            points: Box::pin([Default::default(); N]),
+        })
+    }
+
+    fn get_driver(&mut self) -> &mut lvgl_sys::lv_indev_drv_t {
+        self.device.driver()
+    }
+
+    fn get_descriptor(&mut self) -> Option<&mut lvgl_sys::lv_indev_t> {
+        self.device.descriptor()
+    }
+
+    fn set_feedback<F>(&mut self, feedback: F)
+    where
+        F: FnMut(Event<Infallible>) + 'static,
+    {
+        self.device.set_feedback(feedback)
+    }
+
+    unsafe fn new_raw(
+        read_cb: Option<
+            unsafe extern "C" fn(*mut lvgl_sys::lv_indev_drv_t, *mut lvgl_sys::lv_indev_data_t),
+        >,
+        feedback_cb: Option<unsafe extern "C" fn(*mut lvgl_sys::lv_indev_drv_t, u8)>,
+        _: &crate::Display,
+    ) -> LvResult<Button<N>> {
+        Ok(Self {
+            device: InputDevice::register_raw(
+                lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_BUTTON,
+                read_cb,
+                feedback_cb,
+            )?,
+            points: Box::pin([Default::default(); N]),
+        })
+    }
+
+    unsafe fn set_descriptor(&mut self, descriptor: *mut lvgl_sys::lv_indev_t) -> LvResult<()> {
+        self.device.set_descriptor(descriptor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::widgets::Btn;
+    use crate::{Display, NativeObject, Widget};
+    use core::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    #[test]
+    fn button_input_device() {
+        crate::tests::initialize_test(true);
+        let display = Display::default();
+
+        // Press and release the second button once
+        let mut reads = 0;
+        let read_button_device = move || {
+            reads += 1;
+            match reads {
+                1 => ButtonInputData(1).pressed().once(),
+                _ => ButtonInputData(1).released().once(),
+            }
+        };
+
+        let mut btn = Btn::new().unwrap();
+        btn.set_pos(90, 190);
+        btn.set_size(20, 20);
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let received = events.clone();
+        btn.add_event_handler(None, move |_, event, _| {
+            if matches!(event, Event::Pressed | Event::Clicked) {
+                received.borrow_mut().push(event);
+            }
+        })
+        .unwrap();
+
+        let points = [Point::new(10, 10), Point::new(100, 200)];
+        let button = Button::register_with_points(points, read_button_device, &display).unwrap();
+        assert_eq!(button.points[1].x, 100);
+        assert_eq!(button.points[1].y, 200);
+        for _ in 0..2 {
+            unsafe {
+                lvgl_sys::lv_tick_inc(lvgl_sys::LV_INDEV_DEF_READ_PERIOD);
+                lvgl_sys::lv_timer_handler();
+            }
+        }
+
+        assert_eq!(*events.borrow(), [Event::Pressed, Event::Clicked]);
+        unsafe { lvgl_sys::lv_obj_del(btn.raw().as_ptr()) };
+    }
+
+    #[test]
+    fn unknown_button_is_disabled() {
+        crate::tests::initialize_test(true);
+        let display = Display::default();
+
+        let reads = Rc::new(Cell::new(0));
+        let counter = reads.clone();
+        let read_button_device = move || {
+            counter.set(counter.get() + 1);
+            ButtonInputData(5).pressed().once()
+        };
+        let points = [Point::new(10, 10), Point::new(100, 200)];
+        let _button = Button::register_with_points(points, read_button_device, &display).unwrap();
+        let read = || unsafe {
+            lvgl_sys::lv_tick_inc(lvgl_sys::LV_INDEV_DEF_READ_PERIOD);
+            lvgl_sys::lv_timer_handler();
+        };
+
+        read();
+        read();
+        assert_eq!(reads.get(), 1);
+    }
+}
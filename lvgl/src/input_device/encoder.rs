@@ -1,7 +1,8 @@
+use super::generic::InputDevice;
 use super::{BufferStatus, Data, InputDriver, InputState};
-use crate::Box;
-use crate::{LvError, LvResult};
-use core::mem::MaybeUninit;
+use crate::Event;
+use crate::LvResult;
+use core::convert::Infallible;
 
 /// Encoder-specific input data. Contains the event.
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
@@ -22,48 +23,48 @@ impl EncoderInputData {
     }
 }
 
+impl From<EncoderInputData> for u32 {
+    fn from(value: EncoderInputData) -> Self {
+        match value {
+            EncoderInputData::Press => lvgl_sys::LV_KEY_ENTER,
+            EncoderInputData::LongPress => lvgl_sys::LV_KEY_ENTER,
+            EncoderInputData::TurnLeft => lvgl_sys::LV_KEY_LEFT,
+            EncoderInputData::TurnRight => lvgl_sys::LV_KEY_RIGHT,
+        }
+    }
+}
+
 /// Represents an encoder-type input driver.
 pub struct Encoder {
-    pub(crate) driver: Box<lvgl_sys::lv_indev_drv_t>,
-    pub(crate) descriptor: Option<*mut lvgl_sys::lv_indev_t>,
+    device: InputDevice,
 }
 
 impl InputDriver<Encoder> for Encoder {
-    fn register<F>(handler: F, _: &crate::Display) -> LvResult<Encoder>
+    fn register_fallible<F, E>(handler: F, _: &crate::Display) -> LvResult<Encoder>
     where
-        F: Fn() -> BufferStatus,
+        F: FnMut() -> Result<BufferStatus, E> + 'static,
     {
-        let driver = unsafe {
-            let mut indev_drv = MaybeUninit::uninit();
-            lvgl_sys::lv_indev_drv_init(indev_drv.as_mut_ptr());
-            let mut indev_drv = Box::new(indev_drv.assume_init());
-            indev_drv.type_ = lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_ENCODER;
-            indev_drv.read_cb = Some(read_input::<F>);
-            indev_drv.feedback_cb = Some(feedback);
-            indev_drv.user_data = Box::into_raw(Box::new(handler)) as *mut _;
-            indev_drv
-        };
-
-        let mut dev = Self {
-            driver,
-            descriptor: None,
-        };
-
-        match crate::indev_drv_register(&mut dev) {
-            Ok(()) => Ok(dev),
-            Err(e) => Err(e),
-        }
+        Ok(Self {
+            device: InputDevice::register(
+                lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_ENCODER,
+                handler,
+            )?,
+        })
     }
 
     fn get_driver(&mut self) -> &mut lvgl_sys::lv_indev_drv_t {
-        self.driver.as_mut()
+        self.device.driver()
     }
 
     fn get_descriptor(&mut self) -> Option<&mut lvgl_sys::lv_indev_t> {
-        match self.descriptor {
-            Some(d) => unsafe { d.as_mut() },
-            None => None,
-        }
+        self.device.descriptor()
+    }
+
+    fn set_feedback<F>(&mut self, feedback: F)
+    where
+        F: FnMut(Event<Infallible>) + 'static,
+    {
+        self.device.set_feedback(feedback)
     }
 
     unsafe fn new_raw(
@@ -73,113 +74,32 @@ impl InputDriver<Encoder> for Encoder {
         feedback_cb: Option<unsafe extern "C" fn(*mut lvgl_sys::lv_indev_drv_t, u8)>,
         _: &crate::Display,
     ) -> LvResult<Encoder> {
-        let driver = unsafe {
-            let mut indev_drv = MaybeUninit::uninit();
-            lvgl_sys::lv_indev_drv_init(indev_drv.as_mut_ptr());
-            let mut indev_drv = Box::new(indev_drv.assume_init());
-            indev_drv.type_ = lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_ENCODER;
-            indev_drv.read_cb = read_cb;
-            indev_drv.feedback_cb = feedback_cb;
-            indev_drv
-        };
-
-        let mut dev = Self {
-            driver,
-            descriptor: None,
-        };
-
-        match crate::indev_drv_register(&mut dev) {
-            Ok(()) => Ok(dev),
-            Err(e) => Err(e),
-        }
+        Ok(Self {
+            device: InputDevice::register_raw(
+                lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_ENCODER,
+                read_cb,
+                feedback_cb,
+            )?,
+        })
     }
 
     unsafe fn set_descriptor(&mut self, descriptor: *mut lvgl_sys::lv_indev_t) -> LvResult<()> {
-        if self.descriptor.is_none() {
-            self.descriptor = Some(descriptor);
-        } else {
-            return Err(LvError::AlreadyInUse);
-        }
-        Ok(())
-    }
-}
-
-unsafe extern "C" fn read_input<F>(
-    indev_drv: *mut lvgl_sys::lv_indev_drv_t,
-    data: *mut lvgl_sys::lv_indev_data_t,
-) where
-    F: Fn() -> BufferStatus,
-{
-    // convert user data to function
-    let user_closure = &mut *((*indev_drv).user_data as *mut F);
-    // call user data
-    let info = user_closure();
-    unsafe {
-        (*data).continue_reading = match info {
-            BufferStatus::Once(b) => {
-                (*data).state = match b {
-                    InputState::Pressed(Data::Encoder(d)) => {
-                        (*data).key = match d {
-                            EncoderInputData::Press => lvgl_sys::LV_KEY_ENTER,
-                            EncoderInputData::LongPress => lvgl_sys::LV_KEY_ENTER,
-                            EncoderInputData::TurnLeft => lvgl_sys::LV_KEY_LEFT,
-                            EncoderInputData::TurnRight => lvgl_sys::LV_KEY_RIGHT,
-                        };
-                        lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_PRESSED
-                    }
-                    InputState::Released(Data::Encoder(d)) => {
-                        (*data).key = match d {
-                            EncoderInputData::Press => lvgl_sys::LV_KEY_ENTER,
-                            EncoderInputData::LongPress => lvgl_sys::LV_KEY_ENTER,
-                            EncoderInputData::TurnLeft => lvgl_sys::LV_KEY_LEFT,
-                            EncoderInputData::TurnRight => lvgl_sys::LV_KEY_RIGHT,
-                        };
-                        lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_RELEASED
-                    }
-                    _ => panic!("Non-encoder data returned from encoder device!"),
-                };
-                false
-            }
-            BufferStatus::Buffered(b) => {
-                (*data).state = match b {
-                    InputState::Pressed(Data::Encoder(d)) => {
-                        (*data).key = match d {
-                            EncoderInputData::Press => lvgl_sys::LV_KEY_ENTER,
-                            EncoderInputData::LongPress => lvgl_sys::LV_KEY_ENTER,
-                            EncoderInputData::TurnLeft => lvgl_sys::LV_KEY_LEFT,
-                            EncoderInputData::TurnRight => lvgl_sys::LV_KEY_RIGHT,
-                        };
-                        lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_PRESSED
-                    }
-                    InputState::Released(Data::Encoder(d)) => {
-                        (*data).key = match d {
-                            EncoderInputData::Press => lvgl_sys::LV_KEY_ENTER,
-                            EncoderInputData::LongPress => lvgl_sys::LV_KEY_ENTER,
-                            EncoderInputData::TurnLeft => lvgl_sys::LV_KEY_LEFT,
-                            EncoderInputData::TurnRight => lvgl_sys::LV_KEY_RIGHT,
-                        };
-                        lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_RELEASED
-                    }
-                    _ => panic!("Non-encoder data returned from encoder device!"),
-                };
-                true
-            }
-        }
+        self.device.set_descriptor(descriptor)
     }
 }
 
-unsafe extern "C" fn feedback(_indev_drv: *mut lvgl_sys::lv_indev_drv_t, _code: u8) {}
-
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::Display;
+    use core::cell::Cell;
     use core::marker::PhantomData;
     use embedded_graphics::draw_target::DrawTarget;
     use embedded_graphics::geometry::Size;
     use embedded_graphics::pixelcolor::PixelColor;
     use embedded_graphics::prelude::OriginDimensions;
     use embedded_graphics::Pixel;
+    use std::rc::Rc;
 
     struct FakeDisplay<C>
     where
@@ -223,4 +143,28 @@ mod test {
 
         let _encoder = Encoder::register(read_encoder_device, &display).unwrap();
     }
+
+    #[test]
+    fn encoder_feedback() {
+        crate::tests::initialize_test(true);
+        let display = Display::default();
+
+        let clicks = Rc::new(Cell::new(0));
+        let mut encoder =
+            Encoder::register(|| EncoderInputData::Press.released().once(), &display).unwrap();
+        let counter = clicks.clone();
+        encoder.set_feedback(move |event| {
+            if event == Event::Clicked {
+                counter.set(counter.get() + 1);
+            }
+        });
+
+        let driver = encoder.get_driver();
+        let feedback = driver.feedback_cb.unwrap();
+        unsafe {
+            feedback(driver, lvgl_sys::lv_event_code_t_LV_EVENT_CLICKED as u8);
+            feedback(driver, lvgl_sys::lv_event_code_t_LV_EVENT_PRESSED as u8);
+        }
+        assert_eq!(clicks.get(), 1);
+    }
 }
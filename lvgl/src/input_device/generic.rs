@@ -1,6 +1,13 @@
+use super::button::*;
 use super::encoder::*;
+use super::keypad::*;
 use super::pointer::*;
-use crate::LvResult;
+use crate::Box;
+use crate::{Direction, Event, LvError, LvResult, NativeObject, Point};
+use core::convert::Infallible;
+use core::ffi::c_void;
+use core::mem::MaybeUninit;
+use core::ptr::{self, NonNull};
 
 /// Generic data which can be associated with an input device driver. Varies
 /// based on the concrete type of the input device driver
@@ -10,6 +17,10 @@ pub enum Data {
     Pointer(PointerInputData),
     /// Encoder-specific data.
     Encoder(EncoderInputData),
+    /// Keypad-specific data.
+    Keypad(KeypadInputData),
+    /// Button-specific data.
+    Button(ButtonInputData),
 }
 
 /// Boolean states for an input.
@@ -43,11 +54,24 @@ pub enum BufferStatus {
 
 /// A generic input driver trait.
 pub trait InputDriver<D> {
-    /// Creates an instance of a given input device, given a handler function.
-    /// A `Display` must already have been created.
-    fn register<F>(handler: F, display: &crate::Display) -> LvResult<D>
+    /// Creates an instance of a given input device, given a handler function
+    /// which is called by LVGL to read the state of the device. The handler
+    /// is dropped together with the device, and as LVGL may call it for as
+    /// long as the device is registered, it must not borrow anything. A
+    /// `Display` must already have been created.
+    fn register<F>(mut handler: F, display: &crate::Display) -> LvResult<D>
     where
-        F: Fn() -> BufferStatus;
+        F: FnMut() -> BufferStatus + 'static,
+    {
+        Self::register_fallible(move || Ok::<_, Infallible>(handler()), display)
+    }
+
+    /// Creates an instance of a given input device, given a handler function
+    /// that may fail to read the state of the device. If it returns an error,
+    /// the device is disabled until it is enabled again with `enable`.
+    fn register_fallible<F, E>(handler: F, display: &crate::Display) -> LvResult<D>
+    where
+        F: FnMut() -> Result<BufferStatus, E> + 'static;
 
     /// Returns a pointer to the underlying raw driver.
     fn get_driver(&mut self) -> &mut lvgl_sys::lv_indev_drv_t;
@@ -55,6 +79,76 @@ pub trait InputDriver<D> {
     /// Returns a pointer to the descriptor.
     fn get_descriptor(&mut self) -> Option<&mut lvgl_sys::lv_indev_t>;
 
+    /// Sets a handler function which is called by LVGL on events of the
+    /// input device, such as presses and clicks, e.g. to give haptic or
+    /// audible feedback. Replaces the previous feedback handler. Like the read
+    /// handler, it must not borrow anything.
+    fn set_feedback<F>(&mut self, feedback: F)
+    where
+        F: FnMut(Event<Infallible>) + 'static;
+
+    /// Enables or disables reading from the input device.
+    fn enable(&mut self, enable: bool) -> LvResult<()> {
+        let descriptor = self.get_descriptor().ok_or(LvError::InvalidReference)?;
+        unsafe { lvgl_sys::lv_indev_enable(descriptor, enable) };
+        Ok(())
+    }
+
+    /// Returns the last point read from a pointer or button input device.
+    fn get_point(&mut self) -> LvResult<Point> {
+        let descriptor = self.get_descriptor().ok_or(LvError::InvalidReference)?;
+        let mut point = lvgl_sys::lv_point_t::default();
+        unsafe { lvgl_sys::lv_indev_get_point(descriptor, &mut point) };
+        Ok(Point::new(point.x as i32, point.y as i32))
+    }
+
+    /// Returns how much a pointer input device moved since it was last read.
+    fn get_vect(&mut self) -> LvResult<Point> {
+        let descriptor = self.get_descriptor().ok_or(LvError::InvalidReference)?;
+        let mut point = lvgl_sys::lv_point_t::default();
+        unsafe { lvgl_sys::lv_indev_get_vect(descriptor, &mut point) };
+        Ok(Point::new(point.x as i32, point.y as i32))
+    }
+
+    /// Returns the direction of the current gesture of a pointer input
+    /// device, which is empty if there is none.
+    fn gesture_dir(&mut self) -> LvResult<Direction> {
+        let descriptor = self.get_descriptor().ok_or(LvError::InvalidReference)?;
+        let dir = unsafe { lvgl_sys::lv_indev_get_gesture_dir(descriptor) };
+        Ok(Direction::from_bits_truncate(dir as u8))
+    }
+
+    /// Returns the direction a pointer input device is scrolling in, which is
+    /// empty if it is not scrolling.
+    fn scroll_dir(&mut self) -> LvResult<Direction> {
+        let descriptor = self.get_descriptor().ok_or(LvError::InvalidReference)?;
+        let dir = unsafe { lvgl_sys::lv_indev_get_scroll_dir(descriptor) };
+        Ok(Direction::from_bits_truncate(dir as u8))
+    }
+
+    /// Ignores the input device until it is released.
+    fn wait_release(&mut self) -> LvResult<()> {
+        let descriptor = self.get_descriptor().ok_or(LvError::InvalidReference)?;
+        unsafe { lvgl_sys::lv_indev_wait_release(descriptor) };
+        Ok(())
+    }
+
+    /// Resets the state of the input device, e.g. stopping any ongoing press
+    /// or scroll.
+    fn reset(&mut self) -> LvResult<()> {
+        let descriptor = self.get_descriptor().ok_or(LvError::InvalidReference)?;
+        unsafe { lvgl_sys::lv_indev_reset(descriptor, ptr::null_mut()) };
+        Ok(())
+    }
+
+    /// Sets an object to be moved along with a pointer input device, such as
+    /// the image of a mouse cursor. The object must outlive the device.
+    fn set_cursor(&mut self, cursor: &mut impl NativeObject) -> LvResult<()> {
+        let descriptor = self.get_descriptor().ok_or(LvError::InvalidReference)?;
+        unsafe { lvgl_sys::lv_indev_set_cursor(descriptor, cursor.raw().as_ptr()) };
+        Ok(())
+    }
+
     /// Creates a new `InputDriver` from raw parts.
     ///
     /// # Safety
@@ -77,3 +171,225 @@ pub trait InputDriver<D> {
     /// instance of an `lv_indev_t`, and must also be aligned.
     unsafe fn set_descriptor(&mut self, descriptor: *mut lvgl_sys::lv_indev_t) -> LvResult<()>;
 }
+
+/// A closure owned by an input device, which is freed when the device is
+/// dropped.
+struct BoxedHandler {
+    ptr: NonNull<c_void>,
+    drop: unsafe fn(NonNull<c_void>),
+}
+
+impl BoxedHandler {
+    fn new<F: 'static>(handler: F) -> Self {
+        unsafe fn drop_handler<F>(ptr: NonNull<c_void>) {
+            drop(Box::from_raw(ptr.as_ptr() as *mut F));
+        }
+        Self {
+            // Safety: `Box::into_raw` never returns a null pointer.
+            ptr: unsafe { NonNull::new_unchecked(Box::into_raw(Box::new(handler)) as *mut c_void) },
+            drop: drop_handler::<F>,
+        }
+    }
+
+    fn as_ptr(&self) -> *mut c_void {
+        self.ptr.as_ptr()
+    }
+}
+
+impl Drop for BoxedHandler {
+    fn drop(&mut self) {
+        unsafe { (self.drop)(self.ptr) }
+    }
+}
+
+/// The closures of an input device. The `user_data` of every driver points
+/// to its table; each trampoline knows the concrete type of the closure it
+/// reads.
+#[derive(Default)]
+struct InputHandlers {
+    read: Option<BoxedHandler>,
+    feedback: Option<BoxedHandler>,
+}
+
+impl InputHandlers {
+    fn with_read<F: 'static>(read: F) -> Self {
+        Self {
+            read: Some(BoxedHandler::new(read)),
+            feedback: None,
+        }
+    }
+}
+
+/// The parts shared by every input device: the LVGL driver, the descriptor
+/// LVGL returned when registering it, and the closures it calls. The device
+/// is removed from LVGL when dropped.
+pub(crate) struct InputDevice {
+    driver: Box<lvgl_sys::lv_indev_drv_t>,
+    descriptor: Option<*mut lvgl_sys::lv_indev_t>,
+    // Pointed to by the user data of the driver, and dropped after the
+    // device has been removed from LVGL in `drop`
+    handlers: Box<InputHandlers>,
+}
+
+impl InputDevice {
+    /// Registers a device of the given type, reading its state with
+    /// `handler`.
+    pub(crate) fn register<F, E>(type_: lvgl_sys::lv_indev_type_t, handler: F) -> LvResult<Self>
+    where
+        F: FnMut() -> Result<BufferStatus, E> + 'static,
+    {
+        let handlers = InputHandlers::with_read(handler);
+        unsafe { Self::register_with(type_, Some(read_input::<F, E>), None, handlers) }
+    }
+
+    /// Registers a device of the given type calling raw callbacks.
+    ///
+    /// # Safety
+    ///
+    /// The provided functions must not themselves cause undefined behavior
+    /// when called by LVGL.
+    pub(crate) unsafe fn register_raw(
+        type_: lvgl_sys::lv_indev_type_t,
+        read_cb: Option<
+            unsafe extern "C" fn(*mut lvgl_sys::lv_indev_drv_t, *mut lvgl_sys::lv_indev_data_t),
+        >,
+        feedback_cb: Option<unsafe extern "C" fn(*mut lvgl_sys::lv_indev_drv_t, u8)>,
+    ) -> LvResult<Self> {
+        Self::register_with(type_, read_cb, feedback_cb, InputHandlers::default())
+    }
+
+    unsafe fn register_with(
+        type_: lvgl_sys::lv_indev_type_t,
+        read_cb: Option<
+            unsafe extern "C" fn(*mut lvgl_sys::lv_indev_drv_t, *mut lvgl_sys::lv_indev_data_t),
+        >,
+        feedback_cb: Option<unsafe extern "C" fn(*mut lvgl_sys::lv_indev_drv_t, u8)>,
+        handlers: InputHandlers,
+    ) -> LvResult<Self> {
+        let mut handlers = Box::new(handlers);
+        let mut indev_drv = MaybeUninit::uninit();
+        lvgl_sys::lv_indev_drv_init(indev_drv.as_mut_ptr());
+        let mut driver = Box::new(indev_drv.assume_init());
+        driver.type_ = type_;
+        driver.read_cb = read_cb;
+        driver.feedback_cb = feedback_cb;
+        driver.user_data = handlers.as_mut() as *mut InputHandlers as *mut _;
+
+        let descriptor = lvgl_sys::lv_indev_drv_register(driver.as_mut());
+        if descriptor.is_null() {
+            return Err(LvError::LvOOMemory);
+        }
+        Ok(Self {
+            driver,
+            descriptor: Some(descriptor),
+            handlers,
+        })
+    }
+
+    pub(crate) fn driver(&mut self) -> &mut lvgl_sys::lv_indev_drv_t {
+        self.driver.as_mut()
+    }
+
+    pub(crate) fn descriptor(&mut self) -> Option<&mut lvgl_sys::lv_indev_t> {
+        self.descriptor.and_then(|d| unsafe { d.as_mut() })
+    }
+
+    pub(crate) fn set_descriptor(&mut self, descriptor: *mut lvgl_sys::lv_indev_t) -> LvResult<()> {
+        if self.descriptor.is_some() {
+            return Err(LvError::AlreadyInUse);
+        }
+        self.descriptor = Some(descriptor);
+        Ok(())
+    }
+
+    /// Replaces the feedback closure of the driver.
+    pub(crate) fn set_feedback<F>(&mut self, feedback: F)
+    where
+        F: FnMut(Event<Infallible>) + 'static,
+    {
+        self.handlers.feedback = Some(BoxedHandler::new(feedback));
+        self.driver.feedback_cb = Some(feedback_input::<F>);
+    }
+}
+
+impl Drop for InputDevice {
+    fn drop(&mut self) {
+        if let Some(descriptor) = self.descriptor.take() {
+            unsafe { lvgl_sys::lv_indev_delete(descriptor) }
+        }
+    }
+}
+
+unsafe extern "C" fn read_input<F, E>(
+    indev_drv: *mut lvgl_sys::lv_indev_drv_t,
+    data: *mut lvgl_sys::lv_indev_data_t,
+) where
+    F: FnMut() -> Result<BufferStatus, E>,
+{
+    // convert user data to function
+    let handlers = &*((*indev_drv).user_data as *const InputHandlers);
+    let Some(read) = &handlers.read else {
+        return;
+    };
+    let user_closure = &mut *(read.as_ptr() as *mut F);
+    // call user data
+    match user_closure() {
+        Ok(info) => write_input((*indev_drv).type_, info, &mut *data),
+        Err(_) => {
+            // The device being read is the active one; stop reading from it
+            // until it is enabled again
+            lvgl_sys::lv_indev_enable(lvgl_sys::lv_indev_get_act(), false);
+            (*data).state = lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_RELEASED;
+            (*data).continue_reading = false;
+        }
+    }
+}
+
+fn write_input(
+    indev_type: lvgl_sys::lv_indev_type_t,
+    info: BufferStatus,
+    data: &mut lvgl_sys::lv_indev_data_t,
+) {
+    let (input, continue_reading) = match info {
+        BufferStatus::Once(input) => (input, false),
+        BufferStatus::Buffered(input) => (input, true),
+    };
+    let (state, input_data) = match input {
+        InputState::Pressed(d) => (lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_PRESSED, d),
+        InputState::Released(d) => (lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_RELEASED, d),
+    };
+    match (indev_type, input_data) {
+        (
+            lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_POINTER,
+            Data::Pointer(PointerInputData::Touch(point)),
+        ) => {
+            data.point.x = point.x as lvgl_sys::lv_coord_t;
+            data.point.y = point.y as lvgl_sys::lv_coord_t;
+        }
+        (
+            lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_POINTER,
+            Data::Pointer(PointerInputData::Key(_)),
+        ) => {}
+        (lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_ENCODER, Data::Encoder(d)) => data.key = d.into(),
+        (lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_KEYPAD, Data::Keypad(d)) => data.key = d.into(),
+        (lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_BUTTON, Data::Button(ButtonInputData(id))) => {
+            data.btn_id = id
+        }
+        _ => panic!("Input data does not match the type of the input device!"),
+    }
+    data.state = state;
+    data.continue_reading = continue_reading;
+}
+
+unsafe extern "C" fn feedback_input<F>(indev_drv: *mut lvgl_sys::lv_indev_drv_t, code: u8)
+where
+    F: FnMut(Event<Infallible>),
+{
+    let handlers = &*((*indev_drv).user_data as *const InputHandlers);
+    if let Some(feedback) = &handlers.feedback {
+        let user_closure = &mut *(feedback.as_ptr() as *mut F);
+        if let Ok(event) = Event::try_from(code as lvgl_sys::lv_event_code_t) {
+            user_closure(event);
+        }
+    }
+}
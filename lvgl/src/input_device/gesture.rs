@@ -0,0 +1,476 @@
+//! Multi-touch gesture recognition.
+//!
+//! A `MultiTouch` device reads up to two contacts from a touch controller. The
+//! primary contact drives a regular `Pointer`, so LVGL can press, scroll and
+//! click as usual, while pinches, rotations and swipes are recognized in Rust
+//! and sent as a `Gesture` to the object under them:
+//!
+//! ```ignore
+//! use lvgl::input_device::gesture::{on_gesture, Contacts, Gesture, MultiTouch};
+//!
+//! let _touch_screen = MultiTouch::register(move || read_contacts(&mut controller), &display)?;
+//! on_gesture(&mut map_view, |_map, gesture| {
+//!     if let Gesture::Pinch { scale } = gesture {
+//!         // Zoom the map by scale / 256
+//!     }
+//! })?;
+//! ```
+//!
+//! Gestures are sent to the topmost object under the contacts. Set the
+//! `ObjFlag::EVENT_BUBBLE` flag on its children to also receive gestures on
+//! top of them.
+
+use super::pointer::{Pointer, PointerInputData};
+use super::{BufferStatus, InputDriver};
+use crate::event::send_custom_event;
+use crate::{CustomEventId, Direction, Event, HandlerId, LvResult, Point, Widget};
+
+/// The contacts reported by a multi-touch controller. The primary contact
+/// is the first finger that touched the screen.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Contacts {
+    pub primary: Option<Point>,
+    pub secondary: Option<Point>,
+}
+
+/// A gesture recognized from the contacts of a `MultiTouch` device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Gesture {
+    /// Two fingers moved apart or together. `scale` is the distance between
+    /// them relative to when the second finger touched, times 256.
+    Pinch { scale: i32 },
+    /// Two fingers rotated around each other, by `degrees` clockwise since
+    /// the second finger touched, between -180 and 180.
+    Rotate { degrees: i16 },
+    /// A single finger was swiped in the given direction and released.
+    Swipe(Direction),
+}
+
+/// Thresholds for recognizing gestures.
+#[derive(Debug, Copy, Clone)]
+pub struct GestureConfig {
+    /// Minimum distance in pixels a finger must move to swipe.
+    pub swipe_min_distance: u32,
+    /// Minimum change of the pinch scale, in 1/256ths, before a new
+    /// `Gesture::Pinch` is emitted.
+    pub pinch_threshold: i32,
+    /// Minimum change of the rotation in degrees before a new
+    /// `Gesture::Rotate` is emitted.
+    pub rotate_threshold: i16,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            swipe_min_distance: 50,
+            pinch_threshold: 16,
+            rotate_threshold: 5,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum State {
+    Idle,
+    Pressed {
+        start: Point,
+        last: Point,
+    },
+    MultiTouch {
+        start_distance: i32,
+        start_angle: i16,
+        last_scale: i32,
+        last_degrees: i16,
+    },
+}
+
+/// Recognizes gestures from a stream of `Contacts`.
+#[derive(Debug, Clone)]
+pub struct GestureRecognizer {
+    config: GestureConfig,
+    state: State,
+}
+
+impl GestureRecognizer {
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            state: State::Idle,
+        }
+    }
+
+    /// Returns whether two fingers are down, or have been since the primary
+    /// finger touched.
+    pub fn is_multi_touch(&self) -> bool {
+        matches!(self.state, State::MultiTouch { .. })
+    }
+
+    /// Feeds the latest contacts to the recognizer, calling `emit` with every
+    /// recognized gesture and the point it happened at.
+    pub fn update(&mut self, contacts: Contacts, mut emit: impl FnMut(Gesture, Point)) {
+        match (contacts.primary, contacts.secondary) {
+            (Some(primary), Some(secondary)) => {
+                let distance = distance(primary, secondary);
+                let angle = angle(primary, secondary);
+                let center =
+                    Point::new((primary.x + secondary.x) / 2, (primary.y + secondary.y) / 2);
+                match &mut self.state {
+                    State::MultiTouch {
+                        start_distance,
+                        start_angle,
+                        last_scale,
+                        last_degrees,
+                    } => {
+                        let scale = if *start_distance > 0 {
+                            distance * 256 / *start_distance
+                        } else {
+                            256
+                        };
+                        if (scale - *last_scale).abs() >= self.config.pinch_threshold {
+                            *last_scale = scale;
+                            emit(Gesture::Pinch { scale }, center);
+                        }
+                        let degrees = normalize_degrees(angle - *start_angle);
+                        if (degrees - *last_degrees).abs() >= self.config.rotate_threshold {
+                            *last_degrees = degrees;
+                            emit(Gesture::Rotate { degrees }, center);
+                        }
+                    }
+                    _ => {
+                        self.state = State::MultiTouch {
+                            start_distance: distance,
+                            start_angle: angle,
+                            last_scale: 256,
+                            last_degrees: 0,
+                        }
+                    }
+                }
+            }
+            (Some(primary), None) => match &mut self.state {
+                State::Idle => {
+                    self.state = State::Pressed {
+                        start: primary,
+                        last: primary,
+                    }
+                }
+                State::Pressed { last, .. } => *last = primary,
+                // Lifting one of two fingers does not start a swipe
+                State::MultiTouch { .. } => {}
+            },
+            (None, _) => {
+                if let State::Pressed { start, last } = self.state {
+                    if let Some(direction) = swipe_direction(start, last, &self.config) {
+                        emit(Gesture::Swipe(direction), start);
+                    }
+                }
+                self.state = State::Idle;
+            }
+        }
+    }
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> Self {
+        Self::new(GestureConfig::default())
+    }
+}
+
+fn distance(a: Point, b: Point) -> i32 {
+    let (dx, dy) = ((b.x - a.x) as i64, (b.y - a.y) as i64);
+    isqrt((dx * dx + dy * dy) as u64) as i32
+}
+
+fn isqrt(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+    // Newton's method, starting above the root
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+fn angle(a: Point, b: Point) -> i16 {
+    unsafe { lvgl_sys::lv_atan2(b.x - a.x, b.y - a.y) as i16 }
+}
+
+fn normalize_degrees(degrees: i16) -> i16 {
+    if degrees > 180 {
+        degrees - 360
+    } else if degrees < -180 {
+        degrees + 360
+    } else {
+        degrees
+    }
+}
+
+fn swipe_direction(start: Point, end: Point, config: &GestureConfig) -> Option<Direction> {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    if dx.unsigned_abs().max(dy.unsigned_abs()) < config.swipe_min_distance {
+        None
+    } else if dx.abs() > dy.abs() {
+        Some(if dx > 0 {
+            Direction::RIGHT
+        } else {
+            Direction::LEFT
+        })
+    } else {
+        Some(if dy > 0 {
+            Direction::BOTTOM
+        } else {
+            Direction::TOP
+        })
+    }
+}
+
+// Registered on first use. LVGL keeps registered ids across `deinit`.
+static mut GESTURE_EVENT: Option<CustomEventId> = None;
+
+/// Returns the custom event with which gestures are sent. Its parameter is
+/// the `Gesture`, read with `EventContext::param::<Gesture>()`.
+pub fn gesture_event() -> CustomEventId {
+    unsafe {
+        let id = GESTURE_EVENT;
+        match id {
+            Some(id) => id,
+            None => {
+                let id = CustomEventId::register();
+                GESTURE_EVENT = Some(id);
+                id
+            }
+        }
+    }
+}
+
+fn send_gesture(disp: *mut lvgl_sys::lv_disp_t, gesture: Gesture, point: Point) {
+    let mut point = lvgl_sys::lv_point_t {
+        x: point.x as lvgl_sys::lv_coord_t,
+        y: point.y as lvgl_sys::lv_coord_t,
+    };
+    unsafe {
+        let mut target =
+            lvgl_sys::lv_indev_search_obj(lvgl_sys::lv_disp_get_layer_top(disp), &mut point);
+        if target.is_null() {
+            target = lvgl_sys::lv_indev_search_obj(lvgl_sys::lv_disp_get_scr_act(disp), &mut point);
+        }
+        if !target.is_null() {
+            send_custom_event(target, gesture_event(), &gesture);
+        }
+    }
+}
+
+/// A touch screen reporting up to two contacts, which recognizes gestures.
+pub struct MultiTouch {
+    pointer: Pointer,
+}
+
+impl MultiTouch {
+    /// Registers a multi-touch device with the default `GestureConfig`, given
+    /// a handler function reading the current contacts.
+    pub fn register<F>(handler: F, display: &crate::Display) -> LvResult<Self>
+    where
+        F: FnMut() -> Contacts + 'static,
+    {
+        Self::register_with_config(handler, GestureConfig::default(), display)
+    }
+
+    /// Registers a multi-touch device, given a handler function reading the
+    /// current contacts.
+    pub fn register_with_config<F>(
+        mut handler: F,
+        config: GestureConfig,
+        display: &crate::Display,
+    ) -> LvResult<Self>
+    where
+        F: FnMut() -> Contacts + 'static,
+    {
+        let disp = display.disp.as_ptr();
+        let mut recognizer = GestureRecognizer::new(config);
+        let mut last_point = Point::new(0, 0);
+        let pointer = Pointer::register(
+            move || -> BufferStatus {
+                let contacts = handler();
+                let was_multi_touch = recognizer.is_multi_touch();
+                recognizer.update(contacts, |gesture, point| {
+                    send_gesture(disp, gesture, point)
+                });
+                if !was_multi_touch && recognizer.is_multi_touch() {
+                    // Keep the primary finger from scrolling or clicking
+                    // while pinching or rotating
+                    unsafe { lvgl_sys::lv_indev_wait_release(lvgl_sys::lv_indev_get_act()) };
+                }
+                match contacts.primary {
+                    Some(point) => {
+                        last_point = point;
+                        PointerInputData::Touch(point).pressed().once()
+                    }
+                    None => PointerInputData::Touch(last_point).released().once(),
+                }
+            },
+            display,
+        )?;
+        Ok(Self { pointer })
+    }
+
+    /// Returns the pointer driven by the primary contact.
+    pub fn pointer(&mut self) -> &mut Pointer {
+        &mut self.pointer
+    }
+}
+
+/// Calls `handler` with every gesture made on the widget. The handler can be
+/// removed with `Widget::remove_event_handler`.
+pub fn on_gesture<'a, W, F>(widget: &mut W, mut handler: F) -> LvResult<HandlerId>
+where
+    W: Widget<'a>,
+    F: FnMut(W, Gesture) + 'static,
+{
+    widget.add_event_handler(
+        Some(Event::Custom(gesture_event())),
+        move |widget, _, context| {
+            if let Some(gesture) = context.param::<Gesture>() {
+                handler(widget, *gesture);
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::widgets::Btn;
+    use crate::Display;
+    use core::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    fn contacts(primary: Option<(i32, i32)>, secondary: Option<(i32, i32)>) -> Contacts {
+        Contacts {
+            primary: primary.map(|(x, y)| Point::new(x, y)),
+            secondary: secondary.map(|(x, y)| Point::new(x, y)),
+        }
+    }
+
+    fn gestures(recognizer: &mut GestureRecognizer, contacts: Contacts) -> [Option<Gesture>; 2] {
+        let mut gestures = [None; 2];
+        let mut count = 0;
+        recognizer.update(contacts, |gesture, _| {
+            gestures[count] = Some(gesture);
+            count += 1;
+        });
+        gestures
+    }
+
+    #[test]
+    fn recognize_pinch_and_rotate() {
+        let mut recognizer = GestureRecognizer::default();
+        let start = contacts(Some((100, 100)), Some((200, 100)));
+        assert_eq!(gestures(&mut recognizer, start), [None, None]);
+        assert!(recognizer.is_multi_touch());
+
+        let zoomed = contacts(Some((50, 100)), Some((250, 100)));
+        assert_eq!(
+            gestures(&mut recognizer, zoomed),
+            [Some(Gesture::Pinch { scale: 512 }), None]
+        );
+
+        // Same distance, a quarter turn
+        let rotated = contacts(Some((150, 0)), Some((150, 200)));
+        match gestures(&mut recognizer, rotated) {
+            [Some(Gesture::Rotate { degrees }), None] => {
+                assert!((85..=95).contains(&degrees.abs()))
+            }
+            other => panic!("expected a rotation, got {:?}", other),
+        }
+
+        // Lifting a finger ends the gesture without a swipe
+        let lifted = contacts(Some((150, 0)), None);
+        assert_eq!(gestures(&mut recognizer, lifted), [None, None]);
+        assert_eq!(gestures(&mut recognizer, Contacts::default()), [None, None]);
+        assert!(!recognizer.is_multi_touch());
+    }
+
+    #[test]
+    fn recognize_swipe() {
+        let mut recognizer = GestureRecognizer::default();
+        gestures(&mut recognizer, contacts(Some((100, 100)), None));
+        gestures(&mut recognizer, contacts(Some((110, 160)), None));
+        assert_eq!(
+            gestures(&mut recognizer, Contacts::default()),
+            [Some(Gesture::Swipe(Direction::BOTTOM)), None]
+        );
+
+        // Too short to swipe
+        gestures(&mut recognizer, contacts(Some((100, 100)), None));
+        gestures(&mut recognizer, contacts(Some((80, 100)), None));
+        assert_eq!(gestures(&mut recognizer, Contacts::default()), [None, None]);
+    }
+
+    #[test]
+    fn multi_touch_gestures() {
+        crate::tests::initialize_test(true);
+        let display = Display::default();
+
+        let mut touch_screen =
+            MultiTouch::register(|| contacts(Some((10, 10)), Some((20, 20))), &display).unwrap();
+        assert!(touch_screen.pointer().get_point().is_ok());
+
+        let mut btn = Btn::new().unwrap();
+        let gestures = Rc::new(Cell::new(0));
+        let swiped = gestures.clone();
+        on_gesture(&mut btn, move |_btn, gesture| {
+            assert_eq!(gesture, Gesture::Swipe(Direction::LEFT));
+            swiped.set(swiped.get() + 1);
+        })
+        .unwrap();
+        let swipe = Gesture::Swipe(Direction::LEFT);
+        crate::event_send_with_param(&mut btn, gesture_event(), &swipe).unwrap();
+        crate::event_send_with_param(&mut btn, gesture_event(), &0u8).unwrap();
+        assert_eq!(gestures.get(), 1);
+        unsafe { lvgl_sys::lv_obj_del(crate::NativeObject::raw(&btn).as_ptr()) };
+    }
+
+    #[test]
+    fn pinch_widget_under_contacts() {
+        crate::tests::initialize_test(true);
+        let display = Display::default();
+
+        let mut btn = Btn::new().unwrap();
+        btn.set_pos(60, 60);
+        btn.set_size(120, 80);
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let pinched = received.clone();
+        on_gesture(&mut btn, move |_btn, gesture| {
+            pinched.borrow_mut().push(gesture)
+        })
+        .unwrap();
+
+        // Two fingers touch around (120, 100), move twice as far apart and
+        // are lifted
+        let mut reads = 0;
+        let _touch_screen = MultiTouch::register(
+            move || {
+                reads += 1;
+                match reads {
+                    1 => contacts(Some((100, 100)), Some((140, 100))),
+                    2 => contacts(Some((80, 100)), Some((160, 100))),
+                    _ => Contacts::default(),
+                }
+            },
+            &display,
+        )
+        .unwrap();
+        for _ in 0..3 {
+            unsafe {
+                lvgl_sys::lv_tick_inc(lvgl_sys::LV_INDEV_DEF_READ_PERIOD);
+                lvgl_sys::lv_timer_handler();
+            }
+        }
+
+        assert_eq!(*received.borrow(), [Gesture::Pinch { scale: 512 }]);
+        unsafe { lvgl_sys::lv_obj_del(crate::NativeObject::raw(&btn).as_ptr()) };
+    }
+}
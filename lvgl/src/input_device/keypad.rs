@@ -0,0 +1,216 @@
+use super::generic::InputDevice;
+use super::{BufferStatus, Data, InputDriver, InputState};
+use crate::Event;
+use crate::LvResult;
+use core::convert::Infallible;
+
+/// Keypad-specific input data. Contains the key.
+///
+/// The control keys are used by LVGL to navigate between the objects of a
+/// `Group` and to edit the focused object, and characters are entered into
+/// text areas.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub enum KeypadInputData {
+    Up,
+    Down,
+    Right,
+    Left,
+    Esc,
+    Del,
+    Backspace,
+    Enter,
+    /// Focuses the next object in the group.
+    Next,
+    /// Focuses the previous object in the group.
+    Prev,
+    Home,
+    End,
+    /// A character to enter.
+    Char(char),
+}
+
+impl KeypadInputData {
+    pub fn pressed(self) -> InputState {
+        InputState::Pressed(Data::Keypad(self))
+    }
+
+    pub fn released(self) -> InputState {
+        InputState::Released(Data::Keypad(self))
+    }
+}
+
+impl From<KeypadInputData> for u32 {
+    fn from(value: KeypadInputData) -> Self {
+        match value {
+            KeypadInputData::Up => lvgl_sys::LV_KEY_UP,
+            KeypadInputData::Down => lvgl_sys::LV_KEY_DOWN,
+            KeypadInputData::Right => lvgl_sys::LV_KEY_RIGHT,
+            KeypadInputData::Left => lvgl_sys::LV_KEY_LEFT,
+            KeypadInputData::Esc => lvgl_sys::LV_KEY_ESC,
+            KeypadInputData::Del => lvgl_sys::LV_KEY_DEL,
+            KeypadInputData::Backspace => lvgl_sys::LV_KEY_BACKSPACE,
+            KeypadInputData::Enter => lvgl_sys::LV_KEY_ENTER,
+            KeypadInputData::Next => lvgl_sys::LV_KEY_NEXT,
+            KeypadInputData::Prev => lvgl_sys::LV_KEY_PREV,
+            KeypadInputData::Home => lvgl_sys::LV_KEY_HOME,
+            KeypadInputData::End => lvgl_sys::LV_KEY_END,
+            KeypadInputData::Char(c) => {
+                // LVGL expects characters as their UTF-8 bytes, packed in
+                // memory order
+                let mut bytes = [0; 4];
+                c.encode_utf8(&mut bytes);
+                u32::from_ne_bytes(bytes)
+            }
+        }
+    }
+}
+
+/// Decodes a key code sent with `Event::Key`. Fails for codes which are
+/// neither a control key nor a character.
+impl TryFrom<u32> for KeypadInputData {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        let key = match value {
+            lvgl_sys::LV_KEY_UP => KeypadInputData::Up,
+            lvgl_sys::LV_KEY_DOWN => KeypadInputData::Down,
+            lvgl_sys::LV_KEY_RIGHT => KeypadInputData::Right,
+            lvgl_sys::LV_KEY_LEFT => KeypadInputData::Left,
+            lvgl_sys::LV_KEY_ESC => KeypadInputData::Esc,
+            lvgl_sys::LV_KEY_DEL => KeypadInputData::Del,
+            lvgl_sys::LV_KEY_BACKSPACE => KeypadInputData::Backspace,
+            lvgl_sys::LV_KEY_ENTER => KeypadInputData::Enter,
+            lvgl_sys::LV_KEY_NEXT => KeypadInputData::Next,
+            lvgl_sys::LV_KEY_PREV => KeypadInputData::Prev,
+            lvgl_sys::LV_KEY_HOME => KeypadInputData::Home,
+            lvgl_sys::LV_KEY_END => KeypadInputData::End,
+            _ => {
+                let bytes = value.to_ne_bytes();
+                let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+                let text = core::str::from_utf8(&bytes[..len]).map_err(|_| ())?;
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeypadInputData::Char(c),
+                    _ => return Err(()),
+                }
+            }
+        };
+        Ok(key)
+    }
+}
+
+/// Represents a keypad-type input driver, such as a keyboard or a set of
+/// navigation keys. Associate it with a `Group` to navigate between objects.
+pub struct Keypad {
+    device: InputDevice,
+}
+
+impl InputDriver<Keypad> for Keypad {
+    fn register_fallible<F, E>(handler: F, _: &crate::Display) -> LvResult<Keypad>
+    where
+        F: FnMut() -> Result<BufferStatus, E> + 'static,
+    {
+        Ok(Self {
+            device: InputDevice::register(lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_KEYPAD, handler)?,
+        })
+    }
+
+    fn get_driver(&mut self) -> &mut lvgl_sys::lv_indev_drv_t {
+        self.device.driver()
+    }
+
+    fn get_descriptor(&mut self) -> Option<&mut lvgl_sys::lv_indev_t> {
+        self.device.descriptor()
+    }
+
+    fn set_feedback<F>(&mut self, feedback: F)
+    where
+        F: FnMut(Event<Infallible>) + 'static,
+    {
+        self.device.set_feedback(feedback)
+    }
+
+    unsafe fn new_raw(
+        read_cb: Option<
+            unsafe extern "C" fn(*mut lvgl_sys::lv_indev_drv_t, *mut lvgl_sys::lv_indev_data_t),
+        >,
+        feedback_cb: Option<unsafe extern "C" fn(*mut lvgl_sys::lv_indev_drv_t, u8)>,
+        _: &crate::Display,
+    ) -> LvResult<Keypad> {
+        Ok(Self {
+            device: InputDevice::register_raw(
+                lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_KEYPAD,
+                read_cb,
+                feedback_cb,
+            )?,
+        })
+    }
+
+    unsafe fn set_descriptor(&mut self, descriptor: *mut lvgl_sys::lv_indev_t) -> LvResult<()> {
+        self.device.set_descriptor(descriptor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::group::Group;
+    use crate::widgets::Textarea;
+    use crate::{Display, NativeObject};
+    use cstr_core::CStr;
+
+    #[test]
+    fn keypad_input_device() {
+        crate::tests::initialize_test(true);
+        let display = Display::default();
+
+        // Type 'é', pressing and releasing the key once
+        let mut reads = 0;
+        let read_keypad_device = move || {
+            reads += 1;
+            match reads {
+                1 => KeypadInputData::Char('é').pressed().once(),
+                _ => KeypadInputData::Char('é').released().once(),
+            }
+        };
+
+        let mut keypad = Keypad::register(read_keypad_device, &display).unwrap();
+        let mut group = Group::default();
+        let textarea = Textarea::new().unwrap();
+        group.add_obj(&textarea).unwrap();
+        group.set_indev(&mut keypad).unwrap();
+        for _ in 0..2 {
+            unsafe {
+                lvgl_sys::lv_tick_inc(lvgl_sys::LV_INDEV_DEF_READ_PERIOD);
+                lvgl_sys::lv_timer_handler();
+            }
+        }
+
+        let text =
+            unsafe { CStr::from_ptr(lvgl_sys::lv_textarea_get_text(textarea.raw().as_ptr())) };
+        assert_eq!(text.to_str(), Ok("é"));
+        unsafe { lvgl_sys::lv_obj_del(textarea.raw().as_ptr()) };
+    }
+
+    #[test]
+    fn keypad_key_codes() {
+        assert_eq!(u32::from(KeypadInputData::Enter), lvgl_sys::LV_KEY_ENTER);
+        assert_eq!(
+            u32::from(KeypadInputData::Char('a')).to_ne_bytes(),
+            [b'a', 0, 0, 0]
+        );
+        assert_eq!(
+            u32::from(KeypadInputData::Char('é')).to_ne_bytes(),
+            [0xc3, 0xa9, 0, 0]
+        );
+
+        for key in [
+            KeypadInputData::Enter,
+            KeypadInputData::Char('a'),
+            KeypadInputData::Char('é'),
+        ] {
+            assert_eq!(KeypadInputData::try_from(u32::from(key)), Ok(key));
+        }
+        assert!(KeypadInputData::try_from(0).is_err());
+    }
+}
@@ -3,9 +3,9 @@
 //! LVGL supports 4 types of input device. The current status as to support in
 //! this library is:
 //! - Pointer: Fully supported
-//! - Keyboard: Unsupported
-//! - Button: Unsupported
-//! - Encoder: Unsupported
+//! - Keyboard: Supported, see `keypad`
+//! - Button: Supported
+//! - Encoder: Supported
 //!
 //! The general order of operations when creating an input device is
 //! initializing an instance of the desired device, setting a callback function
@@ -16,21 +16,40 @@
 //! use lvgl::input_device::InputDriver;
 //! use lvgl::input_device::pointer::{Pointer, PointerInputData};
 //! use embedded_graphics::prelude::*;
+//! use std::cell::Cell;
+//! use std::rc::Rc;
 //!
 //! fn main() {
 //!     // IMPORTANT: Initialize a display driver first!
 //!     // ...
 //!     // Define the initial state of your input//! 
-//!     let mut latest_touch_status = PointerInputData::Touch(Point::new(0, 0)).released().once();
+//!     let latest_touch_status = Rc::new(Cell::new(
+//!         PointerInputData::Touch(Point::new(0, 0)).released().once(),
+//!     ));
 //!     // Register a new input device that's capable of reading the current state of the input
-//!     let pointer = Pointer::register(|| latest_touch_status, &display).unwrap();
+//!     let status = latest_touch_status.clone();
+//!     let pointer = Pointer::register(move || status.get(), &display).unwrap();
 //!     // ...
 //! }
 //! ```
+//! The input device is removed from LVGL when it is dropped, so keep it alive
+//! for as long as it should be read. Its handlers are called by LVGL as long
+//! as it is registered, so they must be `'static`, sharing state with the rest
+//! of the program through e.g. an `Rc<Cell<_>>`. Handlers which can fail to read their
+//! device can be registered with `InputDriver::register_fallible`. To react
+//! to presses and clicks, e.g. with haptic feedback, set a feedback handler
+//! with `InputDriver::set_feedback`. Touch screens reporting more than one
+//! contact can recognize pinches, rotations and swipes, see `gesture`.
+//!
 //! For a full example, see the `button_click` example.
 
 mod generic;
 pub use generic::*;
 
+pub mod button;
 pub mod encoder;
+pub mod gesture;
+pub mod keypad;
 pub mod pointer;
+#[cfg(feature = "alloc")]
+pub mod recording;
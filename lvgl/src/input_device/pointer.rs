@@ -1,8 +1,9 @@
+use super::generic::InputDevice;
 use super::{BufferStatus, Data, InputDriver, InputState};
-use crate::Box;
+use crate::Event;
+use crate::LvResult;
 use crate::Point;
-use crate::{LvError, LvResult};
-use core::mem::MaybeUninit;
+use core::convert::Infallible;
 
 /// Pointer-specific input data. Contains the point clicked and the key.
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
@@ -23,168 +24,70 @@ impl PointerInputData {
 
 /// Represents a pointer-type input driver.
 pub struct Pointer {
-    pub(crate) driver: Box<lvgl_sys::lv_indev_drv_t>,
-    pub(crate) descriptor: Option<*mut lvgl_sys::lv_indev_t>,
+    device: InputDevice,
 }
 
 impl InputDriver<Pointer> for Pointer {
-    fn register<F>(handler: F, _: &crate::Display) -> LvResult<Self>
+    fn register_fallible<F, E>(handler: F, _: &crate::Display) -> LvResult<Pointer>
     where
-        F: Fn() -> BufferStatus,
+        F: FnMut() -> Result<BufferStatus, E> + 'static,
     {
-        let driver = unsafe {
-            let mut indev_drv = MaybeUninit::uninit();
-            lvgl_sys::lv_indev_drv_init(indev_drv.as_mut_ptr());
-            let mut indev_drv = Box::new(indev_drv.assume_init());
-            indev_drv.type_ = lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_POINTER;
-            indev_drv.read_cb = Some(read_input::<F>);
-            indev_drv.feedback_cb = Some(feedback);
-            indev_drv.user_data = Box::into_raw(Box::new(handler)) as *mut _;
-            indev_drv
-        };
-
-        let mut dev = Self {
-            driver,
-            descriptor: None,
-        };
-
-        match crate::indev_drv_register(&mut dev) {
-            Ok(()) => Ok(dev),
-            Err(e) => Err(e),
-        }
+        Ok(Self {
+            device: InputDevice::register(
+                lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_POINTER,
+                handler,
+            )?,
+        })
     }
 
     fn get_driver(&mut self) -> &mut lvgl_sys::lv_indev_drv_t {
-        self.driver.as_mut()
+        self.device.driver()
     }
 
     fn get_descriptor(&mut self) -> Option<&mut lvgl_sys::lv_indev_t> {
-        match self.descriptor {
-            Some(d) => unsafe { d.as_mut() },
-            None => None,
-        }
+        self.device.descriptor()
+    }
+
+    fn set_feedback<F>(&mut self, feedback: F)
+    where
+        F: FnMut(Event<Infallible>) + 'static,
+    {
+        self.device.set_feedback(feedback)
     }
 
     unsafe fn new_raw(
         read_cb: Option<
-            unsafe extern "C" fn(*mut lvgl_sys::_lv_indev_drv_t, *mut lvgl_sys::lv_indev_data_t),
+            unsafe extern "C" fn(*mut lvgl_sys::lv_indev_drv_t, *mut lvgl_sys::lv_indev_data_t),
         >,
-        feedback_cb: Option<unsafe extern "C" fn(*mut lvgl_sys::_lv_indev_drv_t, u8)>,
+        feedback_cb: Option<unsafe extern "C" fn(*mut lvgl_sys::lv_indev_drv_t, u8)>,
         _: &crate::Display,
-    ) -> LvResult<Self> {
-        let driver = unsafe {
-            let mut indev_drv = MaybeUninit::uninit();
-            lvgl_sys::lv_indev_drv_init(indev_drv.as_mut_ptr());
-            let mut indev_drv = Box::new(indev_drv.assume_init());
-            indev_drv.type_ = lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_POINTER;
-            indev_drv.read_cb = read_cb;
-            indev_drv.feedback_cb = feedback_cb;
-            indev_drv
-        };
-
-        let mut dev = Self {
-            driver,
-            descriptor: None,
-        };
-
-        match crate::indev_drv_register(&mut dev) {
-            Ok(()) => Ok(dev),
-            Err(e) => Err(e),
-        }
+    ) -> LvResult<Pointer> {
+        Ok(Self {
+            device: InputDevice::register_raw(
+                lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_POINTER,
+                read_cb,
+                feedback_cb,
+            )?,
+        })
     }
 
     unsafe fn set_descriptor(&mut self, descriptor: *mut lvgl_sys::lv_indev_t) -> LvResult<()> {
-        if self.descriptor.is_none() {
-            self.descriptor = Some(descriptor);
-        } else {
-            return Err(LvError::AlreadyInUse);
-        }
-        Ok(())
+        self.device.set_descriptor(descriptor)
     }
 }
 
-unsafe extern "C" fn read_input<F>(
-    indev_drv: *mut lvgl_sys::lv_indev_drv_t,
-    data: *mut lvgl_sys::lv_indev_data_t,
-) where
-    F: Fn() -> BufferStatus,
-{
-    // convert user data to function
-    let user_closure = &mut *((*indev_drv).user_data as *mut F);
-    // call user data
-    let info = user_closure();
-    unsafe {
-        (*data).continue_reading = match info {
-            BufferStatus::Once(b) => {
-                (*data).state = match b {
-                    InputState::Pressed(d) => {
-                        match d {
-                            Data::Pointer(PointerInputData::Touch(point)) => {
-                                (*data).point.x = point.x as lvgl_sys::lv_coord_t;
-                                (*data).point.y = point.y as lvgl_sys::lv_coord_t;
-                            }
-                            Data::Pointer(PointerInputData::Key(_)) => {}
-                            _ => panic!("Non-pointer data returned from pointer device!"),
-                        }
-                        lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_PRESSED
-                    }
-                    InputState::Released(d) => {
-                        match d {
-                            Data::Pointer(PointerInputData::Touch(point)) => {
-                                (*data).point.x = point.x as lvgl_sys::lv_coord_t;
-                                (*data).point.y = point.y as lvgl_sys::lv_coord_t;
-                            }
-                            Data::Pointer(PointerInputData::Key(_)) => {}
-                            _ => panic!("Non-pointer data returned from pointer device!"),
-                        }
-                        lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_RELEASED
-                    }
-                };
-                false
-            }
-            BufferStatus::Buffered(b) => {
-                (*data).state = match b {
-                    InputState::Pressed(d) => {
-                        match d {
-                            Data::Pointer(PointerInputData::Touch(point)) => {
-                                (*data).point.x = point.x as lvgl_sys::lv_coord_t;
-                                (*data).point.y = point.y as lvgl_sys::lv_coord_t;
-                            }
-                            Data::Pointer(PointerInputData::Key(_)) => {}
-                            _ => panic!("Non-pointer data returned from pointer device!"),
-                        }
-                        lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_PRESSED
-                    }
-                    InputState::Released(d) => {
-                        match d {
-                            Data::Pointer(PointerInputData::Touch(point)) => {
-                                (*data).point.x = point.x as lvgl_sys::lv_coord_t;
-                                (*data).point.y = point.y as lvgl_sys::lv_coord_t;
-                            }
-                            Data::Pointer(PointerInputData::Key(_)) => {}
-                            _ => panic!("Non-pointer data returned from pointer device!"),
-                        }
-                        lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_RELEASED
-                    }
-                };
-                true
-            }
-        }
-    }
-}
-
-unsafe extern "C" fn feedback(_indev_drv: *mut lvgl_sys::lv_indev_drv_t, _code: u8) {}
-
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::Display;
+    use crate::{Direction, Display};
+    use core::cell::Cell;
     use core::marker::PhantomData;
     use embedded_graphics::draw_target::DrawTarget;
     use embedded_graphics::geometry::Size;
     use embedded_graphics::pixelcolor::PixelColor;
     use embedded_graphics::prelude::OriginDimensions;
     use embedded_graphics::Pixel;
+    use std::rc::Rc;
 
     struct FakeDisplay<C>
     where
@@ -230,4 +133,74 @@ mod test {
 
         let _touch_screen = Pointer::register(read_touchpad_device, &display).unwrap();
     }
+
+    #[test]
+    fn failing_pointer_is_disabled() {
+        crate::tests::initialize_test(true);
+        let display = Display::default();
+
+        let reads = Rc::new(Cell::new(0));
+        let counter = reads.clone();
+        let mut touch_screen = Pointer::register_fallible(
+            move || {
+                counter.set(counter.get() + 1);
+                Err(())
+            },
+            &display,
+        )
+        .unwrap();
+        let read = || unsafe {
+            lvgl_sys::lv_tick_inc(lvgl_sys::LV_INDEV_DEF_READ_PERIOD);
+            lvgl_sys::lv_timer_handler();
+        };
+
+        read();
+        read();
+        assert_eq!(reads.get(), 1);
+
+        touch_screen.enable(true).unwrap();
+        read();
+        assert_eq!(reads.get(), 2);
+    }
+
+    #[test]
+    fn query_pointer_state() {
+        crate::tests::initialize_test(true);
+        let display = Display::default();
+
+        let mut touch_screen = Pointer::register(
+            || {
+                PointerInputData::Touch(Point::new(120, 23))
+                    .pressed()
+                    .once()
+            },
+            &display,
+        )
+        .unwrap();
+        unsafe {
+            lvgl_sys::lv_tick_inc(lvgl_sys::LV_INDEV_DEF_READ_PERIOD);
+            lvgl_sys::lv_timer_handler();
+        }
+
+        assert_eq!(touch_screen.get_point().unwrap(), Point::new(120, 23));
+        assert_eq!(touch_screen.gesture_dir().unwrap(), Direction::empty());
+        touch_screen.wait_release().unwrap();
+        touch_screen.reset().unwrap();
+    }
+
+    #[test]
+    fn stateful_pointer() {
+        crate::tests::initialize_test(true);
+        let display = Display::default();
+
+        let mut x = 0;
+        let _touch_screen = Pointer::register(
+            move || {
+                x += 1;
+                PointerInputData::Touch(Point::new(x, 0)).pressed().once()
+            },
+            &display,
+        )
+        .unwrap();
+    }
 }
@@ -0,0 +1,455 @@
+//! Recording and replaying input.
+//!
+//! The states read from an input device can be recorded with their tick
+//! timestamps into an `InputLog`, e.g. on the hardware where a bug shows up,
+//! and replayed later into a virtual device, e.g. in a test against a headless
+//! display:
+//!
+//! ```ignore
+//! use lvgl::input_device::recording::{InputLog, Replayer};
+//!
+//! // On the device
+//! let log = Rc::new(RefCell::new(InputLog::new()));
+//! let _touch_screen = Pointer::register(InputLog::record(&log, read_touch_screen), &display)?;
+//! // ... use the UI, then save `log.borrow().to_string()` ...
+//!
+//! // In a test
+//! let log: InputLog = include_str!("bug.log").parse()?;
+//! let _touch_screen: Pointer = Replayer::new(log)?.replay(&display)?;
+//! ```
+//!
+//! Logs are serialized as text, with one state per line. Each line holds the
+//! tick, `once` or `buffered`, `pressed` or `released` and the data of the
+//! device, e.g. `1200 once pressed pointer touch 120 23`. Empty lines and
+//! lines starting with `#` are ignored.
+
+use super::button::ButtonInputData;
+use super::encoder::EncoderInputData;
+use super::keypad::KeypadInputData;
+use super::pointer::PointerInputData;
+use super::{BufferStatus, Data, InputDriver, InputState};
+use crate::{LvError, LvResult, Point};
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt;
+use core::str::FromStr;
+
+/// A state read from an input device, and the tick at which it was read.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct LogEntry {
+    pub tick: u32,
+    pub status: BufferStatus,
+}
+
+/// The states read from an input device, in the order they were read.
+/// Consecutive identical `BufferStatus::Once` states are only recorded once,
+/// while buffered states are all kept, as each of them is a separate input.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct InputLog {
+    entries: Vec<LogEntry>,
+}
+
+impl InputLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps the handler function of an input device, recording every state
+    /// it reads into `log`.
+    pub fn record<F>(log: &Rc<RefCell<Self>>, mut handler: F) -> impl FnMut() -> BufferStatus
+    where
+        F: FnMut() -> BufferStatus,
+    {
+        let log = log.clone();
+        move || {
+            let status = handler();
+            log.borrow_mut().push(tick(), status);
+            status
+        }
+    }
+
+    /// Wraps the fallible handler function of an input device, recording
+    /// every state it successfully reads into `log`.
+    pub fn record_fallible<F, E>(
+        log: &Rc<RefCell<Self>>,
+        mut handler: F,
+    ) -> impl FnMut() -> Result<BufferStatus, E>
+    where
+        F: FnMut() -> Result<BufferStatus, E>,
+    {
+        let log = log.clone();
+        move || {
+            let status = handler()?;
+            log.borrow_mut().push(tick(), status);
+            Ok(status)
+        }
+    }
+
+    /// Appends a state read at `tick`, unless it is not buffered and the same
+    /// as the last one.
+    pub fn push(&mut self, tick: u32, status: BufferStatus) {
+        let repeated = self.entries.last().map(|entry| entry.status) == Some(status);
+        if !repeated || matches!(status, BufferStatus::Buffered(_)) {
+            self.entries.push(LogEntry { tick, status });
+        }
+    }
+
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of ticks between the first and the last entry.
+    pub fn duration(&self) -> u32 {
+        match (self.entries.first(), self.entries.last()) {
+            (Some(first), Some(last)) => last.tick.wrapping_sub(first.tick),
+            _ => 0,
+        }
+    }
+}
+
+fn tick() -> u32 {
+    unsafe { lvgl_sys::lv_tick_get() }
+}
+
+/// Replays an `InputLog` as the handler function of an input device, with
+/// the same timing as it was recorded. The first entry is replayed on the
+/// first read, and each read returns the state of the device at the current
+/// tick.
+pub struct Replayer {
+    log: InputLog,
+    start: Option<u32>,
+    next: usize,
+    current: BufferStatus,
+}
+
+impl Replayer {
+    /// Creates a replayer for a log, which must not be empty.
+    pub fn new(log: InputLog) -> LvResult<Self> {
+        let current = log.entries.first().ok_or(LvError::InvalidReference)?.status;
+        Ok(Self {
+            log,
+            start: None,
+            next: 0,
+            current,
+        })
+    }
+
+    /// Returns the state of the device at the current tick. States which
+    /// were superseded by a later due state since the last read are skipped,
+    /// except buffered states, which are all read in order. Afterwards, the
+    /// last state is held.
+    pub fn read(&mut self) -> BufferStatus {
+        let now = tick();
+        let elapsed = now.wrapping_sub(*self.start.get_or_insert(now));
+        let first = self.log.entries[0].tick;
+        let mut replayed = None;
+        while let Some(entry) = self.log.entries.get(self.next) {
+            if entry.tick.wrapping_sub(first) > elapsed {
+                break;
+            }
+            self.next += 1;
+            self.current = entry.status;
+            replayed = Some(entry.status);
+            if let BufferStatus::Buffered(_) = entry.status {
+                break;
+            }
+        }
+        match replayed {
+            Some(status) => status,
+            // Repeating a buffered state would keep LVGL reading forever
+            None => match self.current {
+                BufferStatus::Once(state) | BufferStatus::Buffered(state) => {
+                    BufferStatus::Once(state)
+                }
+            },
+        }
+    }
+
+    /// Returns whether every entry of the log has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.log.entries.len()
+    }
+
+    /// Registers a virtual input device replaying the log. The log must have
+    /// been recorded from the same type of device.
+    pub fn replay<D>(mut self, display: &crate::Display) -> LvResult<D>
+    where
+        D: InputDriver<D>,
+    {
+        D::register(move || self.read(), display)
+    }
+}
+
+impl fmt::Display for InputLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            let (buffering, state) = match entry.status {
+                BufferStatus::Once(state) => ("once", state),
+                BufferStatus::Buffered(state) => ("buffered", state),
+            };
+            let (pressed, data) = match state {
+                InputState::Pressed(data) => ("pressed", data),
+                InputState::Released(data) => ("released", data),
+            };
+            write!(f, "{} {} {} ", entry.tick, buffering, pressed)?;
+            match data {
+                Data::Pointer(PointerInputData::Touch(point)) => {
+                    write!(f, "pointer touch {} {}", point.x, point.y)?
+                }
+                Data::Pointer(PointerInputData::Key(key)) => write!(f, "pointer key {}", key)?,
+                Data::Encoder(data) => write!(f, "encoder {}", encoder_name(data))?,
+                Data::Keypad(KeypadInputData::Char(c)) => write!(f, "keypad char {}", c as u32)?,
+                Data::Keypad(data) => write!(f, "keypad {}", key_name(data))?,
+                Data::Button(ButtonInputData(id)) => write!(f, "button {}", id)?,
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+const ENCODER_NAMES: [(EncoderInputData, &str); 4] = [
+    (EncoderInputData::Press, "press"),
+    (EncoderInputData::LongPress, "long_press"),
+    (EncoderInputData::TurnLeft, "turn_left"),
+    (EncoderInputData::TurnRight, "turn_right"),
+];
+
+const KEY_NAMES: [(KeypadInputData, &str); 12] = [
+    (KeypadInputData::Up, "up"),
+    (KeypadInputData::Down, "down"),
+    (KeypadInputData::Right, "right"),
+    (KeypadInputData::Left, "left"),
+    (KeypadInputData::Esc, "esc"),
+    (KeypadInputData::Del, "del"),
+    (KeypadInputData::Backspace, "backspace"),
+    (KeypadInputData::Enter, "enter"),
+    (KeypadInputData::Next, "next"),
+    (KeypadInputData::Prev, "prev"),
+    (KeypadInputData::Home, "home"),
+    (KeypadInputData::End, "end"),
+];
+
+fn encoder_name(data: EncoderInputData) -> &'static str {
+    ENCODER_NAMES
+        .iter()
+        .find(|(value, _)| *value == data)
+        .map_or("", |(_, name)| name)
+}
+
+fn key_name(data: KeypadInputData) -> &'static str {
+    KEY_NAMES
+        .iter()
+        .find(|(value, _)| *value == data)
+        .map_or("", |(_, name)| name)
+}
+
+/// An error parsing an `InputLog`, with the number of the offending line,
+/// starting at 1.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ParseLogError {
+    pub line: usize,
+}
+
+impl fmt::Display for ParseLogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid input log entry on line {}", self.line)
+    }
+}
+
+impl FromStr for InputLog {
+    type Err = ParseLogError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut log = Self::new();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let entry = parse_entry(line).ok_or(ParseLogError { line: index + 1 })?;
+            log.entries.push(entry);
+        }
+        Ok(log)
+    }
+}
+
+fn parse_entry(line: &str) -> Option<LogEntry> {
+    let mut words = line.split_whitespace();
+    let mut next = || words.next();
+    let tick = next()?.parse().ok()?;
+    let buffered = match next()? {
+        "once" => false,
+        "buffered" => true,
+        _ => return None,
+    };
+    let pressed = match next()? {
+        "pressed" => true,
+        "released" => false,
+        _ => return None,
+    };
+    let data = match next()? {
+        "pointer" => Data::Pointer(match next()? {
+            "touch" => {
+                PointerInputData::Touch(Point::new(next()?.parse().ok()?, next()?.parse().ok()?))
+            }
+            "key" => PointerInputData::Key(next()?.parse().ok()?),
+            _ => return None,
+        }),
+        "encoder" => {
+            let name = next()?;
+            Data::Encoder(ENCODER_NAMES.iter().find(|(_, n)| *n == name)?.0)
+        }
+        "keypad" => Data::Keypad(match next()? {
+            "char" => KeypadInputData::Char(char::from_u32(next()?.parse().ok()?)?),
+            name => KEY_NAMES.iter().find(|(_, n)| *n == name)?.0,
+        }),
+        "button" => Data::Button(ButtonInputData(next()?.parse().ok()?)),
+        _ => return None,
+    };
+    if next().is_some() {
+        return None;
+    }
+    let state = if pressed {
+        InputState::Pressed(data)
+    } else {
+        InputState::Released(data)
+    };
+    let status = if buffered {
+        BufferStatus::Buffered(state)
+    } else {
+        BufferStatus::Once(state)
+    };
+    Some(LogEntry { tick, status })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input_device::keypad::Keypad;
+    use crate::input_device::pointer::Pointer;
+    use crate::Display;
+    use alloc::string::ToString;
+
+    fn advance(ticks: u32) {
+        unsafe {
+            lvgl_sys::lv_tick_inc(ticks);
+            lvgl_sys::lv_timer_handler();
+        }
+    }
+
+    #[test]
+    fn record_and_parse_log() {
+        crate::tests::initialize_test(false);
+        let log = Rc::new(RefCell::new(InputLog::new()));
+        let mut pressed = true;
+        let mut read = InputLog::record(&log, || {
+            PointerInputData::Touch(Point::new(10, -2)).pressed().once()
+        });
+        let mut read_keys = InputLog::record(&log, || {
+            pressed = !pressed;
+            if pressed {
+                KeypadInputData::Char('é').pressed().and_continued()
+            } else {
+                KeypadInputData::Enter.released().once()
+            }
+        });
+
+        read();
+        read();
+        advance(10);
+        read_keys();
+        advance(10);
+        read_keys();
+
+        let log = log.borrow().clone();
+        assert_eq!(log.entries().len(), 3);
+        assert_eq!(log.duration(), 20);
+        let text = log.to_string();
+        assert_eq!(
+            text.lines().nth(1).unwrap().split_once(' ').unwrap().1,
+            "once released keypad enter"
+        );
+        assert_eq!(text.parse::<InputLog>().unwrap(), log);
+
+        assert_eq!(
+            "# Comment\n\n0 once pressed button 1\n10 once pressed button".parse::<InputLog>(),
+            Err(ParseLogError { line: 4 })
+        );
+
+        // Repeated buffered states are separate inputs
+        let mut keys = InputLog::new();
+        let key = KeypadInputData::Char('a').pressed();
+        keys.push(30, key.and_continued());
+        keys.push(30, key.and_continued());
+        keys.push(30, key.once());
+        keys.push(60, key.once());
+        assert_eq!(keys.entries().len(), 3);
+    }
+
+    #[test]
+    fn replay_pointer() {
+        crate::tests::initialize_test(true);
+        let display = Display::default();
+        let log: InputLog = "
+            100 once pressed pointer touch 10 20
+            160 once pressed pointer touch 30 40
+            200 once released pointer touch 30 40
+        "
+        .parse()
+        .unwrap();
+        assert_eq!(log.duration(), 100);
+
+        let mut touch_screen: Pointer = Replayer::new(log).unwrap().replay(&display).unwrap();
+        advance(lvgl_sys::LV_INDEV_DEF_READ_PERIOD);
+        assert_eq!(touch_screen.get_point().unwrap(), Point::new(10, 20));
+        advance(60);
+        assert_eq!(touch_screen.get_point().unwrap(), Point::new(30, 40));
+
+        assert!(Replayer::new(InputLog::new()).is_err());
+        let keys: InputLog = "0 buffered pressed keypad char 97".parse().unwrap();
+        let _keypad: Keypad = Replayer::new(keys).unwrap().replay(&display).unwrap();
+        advance(lvgl_sys::LV_INDEV_DEF_READ_PERIOD);
+    }
+
+    #[test]
+    fn replay_by_tick() {
+        crate::tests::initialize_test(false);
+        let touches: InputLog = "
+            0 once pressed pointer touch 10 20
+            10 once pressed pointer touch 20 20
+            20 once released pointer touch 30 40
+        "
+        .parse()
+        .unwrap();
+        let entries = touches.entries().to_vec();
+        let mut replayer = Replayer::new(touches).unwrap();
+        assert_eq!(replayer.read(), entries[0].status);
+        assert_eq!(replayer.read(), entries[0].status);
+
+        // Read less often than recorded: the touch at tick 10 is skipped
+        advance(30);
+        assert_eq!(replayer.read(), entries[2].status);
+        assert!(replayer.is_finished());
+
+        // Every buffered key is read
+        let keys: InputLog = "
+            0 buffered pressed keypad char 97
+            0 buffered pressed keypad char 97
+            0 once released keypad char 97
+        "
+        .parse()
+        .unwrap();
+        let entries = keys.entries().to_vec();
+        let mut replayer = Replayer::new(keys).unwrap();
+        for entry in &entries {
+            assert_eq!(replayer.read(), entry.status);
+        }
+        assert_eq!(replayer.read(), entries[2].status);
+    }
+}
@@ -64,13 +64,20 @@ mod display;
 mod functions;
 mod support;
 
+#[cfg(feature = "async")]
+pub mod asynch;
 #[cfg(feature = "drivers")]
 pub mod drivers;
 pub mod font;
+#[cfg(feature = "alloc")]
+pub mod framebuffer;
 pub mod input_device;
 pub mod misc;
 pub mod widgets;
 
+#[cfg(feature = "testing")]
+pub mod testing;
+
 #[cfg(feature = "rust_timer")]
 pub mod timer;
 
@@ -79,6 +86,15 @@ static mut IS_INIT: bool = false;
 #[cfg(not(feature = "unsafe_no_autoinit"))]
 static mut IS_INIT: bool = true;
 
+/// The number of times LVGL was deinitialized, telling apart the objects
+/// created before and after.
+static mut GENERATION: usize = 0;
+
+/// Returns the number of times LVGL was deinitialized so far.
+pub(crate) fn generation() -> usize {
+    unsafe { GENERATION }
+}
+
 /// Initializes LVGL. Call at the start of the program, or after safely
 /// deinitializing with `deinit()`.
 pub fn init() {
@@ -103,6 +119,7 @@ pub unsafe fn deinit() {
         if IS_INIT {
             lvgl_sys::lv_deinit();
             IS_INIT = false;
+            GENERATION += 1;
         }
     }
 }
@@ -128,4 +145,19 @@ pub(crate) mod tests {
             let _ = Display::register(buffer, 240, 240, |_| {}).unwrap();
         }
     }
+
+    /// Gives the active screen of `display` a background color.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn set_screen_color(
+        display: &crate::framebuffer::FramebufferDisplay,
+        color: (u8, u8, u8),
+    ) {
+        use crate::{style::Style, Color, Part, Widget};
+
+        let mut screen = display.display().get_scr_act().unwrap();
+        let mut style = Style::default();
+        style.set_bg_color(Color::from_rgb(color));
+        // The style must outlive the screen
+        screen.add_style(Part::Main, Box::leak(Box::new(style)));
+    }
 }
@@ -0,0 +1,479 @@
+//! Event handlers of objects.
+//!
+//! Any number of handlers can be added to an object with
+//! `Widget::add_event_handler`, each for a single kind of event or for all of
+//! them. Handlers are freed when they are removed with
+//! `Widget::remove_event_handler`, or when LVGL deletes the object.
+//!
+//! Events sent to an object with the `ObjFlag::EVENT_BUBBLE` flag are also
+//! sent to its parent. Handlers receive an `EventContext` to tell the object
+//! the event was originally sent to from the one they were added to, and to
+//! stop the event from propagating further:
+//!
+//! ```ignore
+//! list.on_event(|_list, event, context| {
+//!     if event == Event::Clicked {
+//!         let item = context.target();
+//!         // ...
+//!         context.stop_bubbling();
+//!     }
+//! })?;
+//! ```
+
+use crate::{Box, CustomEventId, Event, LvError, LvResult, NativeObject, Obj, Widget};
+use core::any::TypeId;
+use core::cell::Cell;
+use core::ffi::c_void;
+use core::ptr::{self, NonNull};
+
+type EventCallback = unsafe extern "C" fn(*mut lvgl_sys::lv_event_t);
+
+/// Identifies an event handler added to an object, to remove it with
+/// `Widget::remove_event_handler`.
+#[derive(Debug, Copy, Clone)]
+pub struct HandlerId {
+    closure: NonNull<c_void>,
+    handle_cb: EventCallback,
+    free_cb: EventCallback,
+    release: unsafe fn(NonNull<c_void>),
+}
+
+// Every handler has its own closure
+impl PartialEq for HandlerId {
+    fn eq(&self, other: &Self) -> bool {
+        self.closure == other.closure
+    }
+}
+
+impl Eq for HandlerId {}
+
+/// A handler closure, with the state needed to defer freeing it while it
+/// is being called.
+struct Handler<F> {
+    closure: F,
+    // Whether the handler is being called. A handler sending an event to its
+    // own object is not called again until it returns, so that the closure
+    // is never borrowed twice
+    running: Cell<bool>,
+    removed: Cell<bool>,
+}
+
+/// The parameter of custom events sent from Rust, tagged with its type.
+struct CustomParam {
+    type_id: TypeId,
+    value: *const c_void,
+    // The parameter of the event being sent when this one was
+    outer: *const CustomParam,
+}
+
+// The parameters of the custom events being sent from Rust, innermost first.
+// Other code may send custom events with any parameter, so only these are
+// read as a `CustomParam`.
+static mut SENDING: *const CustomParam = ptr::null();
+
+/// Returns whether `param` is the parameter of a custom event being sent
+/// from Rust.
+unsafe fn is_sending(param: *const CustomParam) -> bool {
+    let mut sending = SENDING;
+    while !sending.is_null() {
+        if sending == param {
+            return true;
+        }
+        sending = (*sending).outer;
+    }
+    false
+}
+
+/// Sends a custom event to an object, with a reference to `param` which
+/// handlers can read with `EventContext::param`.
+pub(crate) unsafe fn send_custom_event<P: 'static>(
+    obj: *mut lvgl_sys::lv_obj_t,
+    id: CustomEventId,
+    param: &P,
+) {
+    let mut param = CustomParam {
+        type_id: TypeId::of::<P>(),
+        value: param as *const P as *const c_void,
+        outer: SENDING,
+    };
+    let param = &mut param as *mut CustomParam;
+    SENDING = param;
+    lvgl_sys::lv_event_send(obj, id.code(), param as *mut c_void);
+    SENDING = (*param).outer;
+}
+
+/// The event being handled, passed to every event handler.
+pub struct EventContext {
+    raw: NonNull<lvgl_sys::lv_event_t>,
+}
+
+impl EventContext {
+    /// Returns the object the event was originally sent to. It differs from
+    /// `current_target` if the event bubbled up from a child.
+    pub fn target(&self) -> Obj<'_> {
+        let raw = unsafe { lvgl_sys::lv_event_get_target(self.raw.as_ptr()) };
+        // Safety: events are always sent to an object.
+        unsafe { Obj::from_raw(NonNull::new_unchecked(raw)).unwrap() }
+    }
+
+    /// Returns the object whose handler is being called.
+    pub fn current_target(&self) -> Obj<'_> {
+        let raw = unsafe { lvgl_sys::lv_event_get_current_target(self.raw.as_ptr()) };
+        // Safety: events are always sent to an object.
+        unsafe { Obj::from_raw(NonNull::new_unchecked(raw)).unwrap() }
+    }
+
+    /// Returns the raw code of the event.
+    pub fn code(&self) -> lvgl_sys::lv_event_code_t {
+        unsafe { lvgl_sys::lv_event_get_code(self.raw.as_ptr()) }
+    }
+
+    /// Returns the parameter of a custom event sent with
+    /// `event_send_with_param`, or `None` if the event was sent otherwise,
+    /// e.g. by `lv_event_send`, or its parameter is not a `P`.
+    pub fn param<P: 'static>(&self) -> Option<&P> {
+        let raw = self.raw.as_ptr();
+        unsafe {
+            CustomEventId::from_code(lvgl_sys::lv_event_get_code(raw))?;
+            let param = lvgl_sys::lv_event_get_param(raw) as *const CustomParam;
+            if !is_sending(param) {
+                return None;
+            }
+            let param = &*param;
+            if param.type_id != TypeId::of::<P>() {
+                return None;
+            }
+            (param.value as *const P).as_ref()
+        }
+    }
+
+    /// Keeps the event from bubbling up to the parents of the current
+    /// target. The other handlers of the current target are still called.
+    pub fn stop_bubbling(&mut self) {
+        unsafe { lvgl_sys::lv_event_stop_bubbling(self.raw.as_ptr()) }
+    }
+
+    /// Keeps the event from being sent to any other handler, including the
+    /// remaining handlers of the current target.
+    pub fn stop_processing(&mut self) {
+        unsafe { lvgl_sys::lv_event_stop_processing(self.raw.as_ptr()) }
+    }
+
+    /// Returns the raw event.
+    pub fn raw(&self) -> NonNull<lvgl_sys::lv_event_t> {
+        self.raw
+    }
+}
+
+pub(crate) fn add_event_handler<'a, W, F>(
+    widget: &mut W,
+    filter: Option<Event<W::SpecialEvent>>,
+    handler: F,
+) -> LvResult<HandlerId>
+where
+    W: Widget<'a>,
+    F: FnMut(W, Event<W::SpecialEvent>, &mut EventContext) + 'static,
+{
+    let code = match filter {
+        Some(event) => event.into(),
+        None => lvgl_sys::lv_event_code_t_LV_EVENT_ALL,
+    };
+    let handler = Box::new(Handler {
+        closure: handler,
+        running: Cell::new(false),
+        removed: Cell::new(false),
+    });
+    // Safety: `Box::into_raw` never returns a null pointer.
+    let closure = unsafe { NonNull::new_unchecked(Box::into_raw(handler) as *mut c_void) };
+    let id = HandlerId {
+        closure,
+        handle_cb: handle_event::<'a, W, F>,
+        free_cb: free_handler::<F>,
+        release: release_handler::<F>,
+    };
+    unsafe {
+        let obj = widget.raw().as_ptr();
+        // LVGL calls the callbacks of an object in the order they were added,
+        // so the handler still sees the `Delete` event before it is freed
+        lvgl_sys::lv_obj_add_event_cb(obj, Some(id.handle_cb), code, closure.as_ptr());
+        lvgl_sys::lv_obj_add_event_cb(
+            obj,
+            Some(id.free_cb),
+            lvgl_sys::lv_event_code_t_LV_EVENT_DELETE,
+            closure.as_ptr(),
+        );
+    }
+    Ok(id)
+}
+
+pub(crate) fn remove_event_handler(widget: &impl NativeObject, id: HandlerId) -> LvResult<()> {
+    unsafe {
+        let obj = widget.raw().as_ptr();
+        // Only free the closure if it still belongs to this object, so that
+        // removing a handler twice is harmless
+        if !lvgl_sys::lv_obj_remove_event_cb_with_user_data(
+            obj,
+            Some(id.free_cb),
+            id.closure.as_ptr(),
+        ) {
+            return Err(LvError::InvalidReference);
+        }
+        lvgl_sys::lv_obj_remove_event_cb_with_user_data(
+            obj,
+            Some(id.handle_cb),
+            id.closure.as_ptr(),
+        );
+        (id.release)(id.closure);
+    }
+    Ok(())
+}
+
+unsafe extern "C" fn handle_event<'a, W, F>(event: *mut lvgl_sys::lv_event_t)
+where
+    W: Widget<'a>,
+    F: FnMut(W, Event<W::SpecialEvent>, &mut EventContext),
+{
+    let handler = lvgl_sys::lv_event_get_user_data(event) as *mut Handler<F>;
+    // The handler was added to the current target; the target may be one of
+    // its children of another type if the event bubbled up
+    let obj = lvgl_sys::lv_event_get_current_target(event);
+    if let (Some(obj), Some(raw), Some(code)) = (
+        NonNull::new(obj),
+        NonNull::new(event),
+        Event::from_raw(event),
+    ) {
+        let running = &(*handler).running;
+        if running.get() {
+            return;
+        }
+        if let Some(widget) = W::from_raw(obj) {
+            running.set(true);
+            ((*handler).closure)(widget, code, &mut EventContext { raw });
+            running.set(false);
+            // The handler removed itself while it was running
+            if (*handler).removed.get() {
+                drop(Box::from_raw(handler));
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn free_handler<F>(event: *mut lvgl_sys::lv_event_t) {
+    if let Some(closure) = NonNull::new(lvgl_sys::lv_event_get_user_data(event)) {
+        release_handler::<F>(closure);
+    }
+}
+
+/// Frees a handler, or marks it to be freed once it returns if it is being
+/// called.
+unsafe fn release_handler<F>(closure: NonNull<c_void>) {
+    let handler = closure.as_ptr() as *mut Handler<F>;
+    if (*handler).running.get() {
+        (*handler).removed.set(true);
+    } else {
+        drop(Box::from_raw(handler));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::widgets::Btn;
+    use crate::{
+        event_send, event_send_with_param, CustomEventId, Event, NativeObject, Obj, ObjFlag, Widget,
+    };
+    use core::cell::Cell;
+    use std::rc::Rc;
+
+    struct DropFlag(Rc<Cell<bool>>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    #[test]
+    fn add_and_remove_event_handlers() {
+        crate::tests::initialize_test(true);
+        let mut btn = Btn::new().unwrap();
+        let clicks = Rc::new(Cell::new(0));
+        let events = Rc::new(Cell::new(0));
+        let (clicked, received) = (clicks.clone(), events.clone());
+        let click_handler = btn
+            .add_event_handler(Some(Event::Clicked), move |_, _, _| {
+                clicked.set(clicked.get() + 1)
+            })
+            .unwrap();
+        btn.add_event_handler(None, move |_, _, _| received.set(received.get() + 1))
+            .unwrap();
+
+        event_send(&mut btn, Event::Clicked).unwrap();
+        event_send(&mut btn, Event::Pressed).unwrap();
+        assert_eq!(clicks.get(), 1);
+        assert_eq!(events.get(), 2);
+
+        btn.remove_event_handler(click_handler).unwrap();
+        assert!(btn.remove_event_handler(click_handler).is_err());
+        event_send(&mut btn, Event::Clicked).unwrap();
+        assert_eq!(clicks.get(), 1);
+        assert_eq!(events.get(), 3);
+        unsafe { lvgl_sys::lv_obj_del(btn.raw().as_ptr()) };
+    }
+
+    #[test]
+    fn remove_event_handler_from_itself() {
+        crate::tests::initialize_test(true);
+        let calls = Rc::new(Cell::new(0));
+        let dropped = Rc::new(Cell::new(false));
+        let own_id = Rc::new(Cell::new(None));
+        let mut btn = Btn::new().unwrap();
+        let flag = DropFlag(dropped.clone());
+        let (handler_calls, handler_id) = (calls.clone(), own_id.clone());
+        let id = btn
+            .add_event_handler(Some(Event::Clicked), move |mut btn, _, _| {
+                btn.remove_event_handler(handler_id.get().unwrap()).unwrap();
+                // The closure is only freed once it returns
+                assert!(!flag.0.get());
+                handler_calls.set(handler_calls.get() + 1);
+            })
+            .unwrap();
+        own_id.set(Some(id));
+
+        event_send(&mut btn, Event::Clicked).unwrap();
+        assert_eq!(calls.get(), 1);
+        assert!(dropped.get());
+        event_send(&mut btn, Event::Clicked).unwrap();
+        assert_eq!(calls.get(), 1);
+        unsafe { lvgl_sys::lv_obj_del(btn.raw().as_ptr()) };
+    }
+
+    #[test]
+    fn send_event_from_handler() {
+        crate::tests::initialize_test(true);
+        let mut btn = Btn::new().unwrap();
+        let clicks = Rc::new(Cell::new(0));
+        let presses = Rc::new(Cell::new(0));
+        let (clicked, pressed) = (clicks.clone(), presses.clone());
+        btn.add_event_handler(None, move |mut btn, event, _| match event {
+            Event::Clicked => {
+                clicked.set(clicked.get() + 1);
+                event_send(&mut btn, Event::Clicked).unwrap();
+                event_send(&mut btn, Event::Pressed).unwrap();
+            }
+            Event::Pressed => pressed.set(pressed.get() + 1),
+            _ => {}
+        })
+        .unwrap();
+        let others = Rc::new(Cell::new(0));
+        let other_clicks = others.clone();
+        btn.add_event_handler(Some(Event::Clicked), move |_, _, _| {
+            other_clicks.set(other_clicks.get() + 1)
+        })
+        .unwrap();
+
+        // The nested events skip the handler sending them
+        event_send(&mut btn, Event::Clicked).unwrap();
+        assert_eq!(clicks.get(), 1);
+        assert_eq!(presses.get(), 0);
+        assert_eq!(others.get(), 2);
+        event_send(&mut btn, Event::Pressed).unwrap();
+        assert_eq!(presses.get(), 1);
+        unsafe { lvgl_sys::lv_obj_del(btn.raw().as_ptr()) };
+    }
+
+    #[test]
+    fn event_handlers_freed_on_delete() {
+        crate::tests::initialize_test(true);
+        let mut btn = Btn::new().unwrap();
+        let dropped = Rc::new(Cell::new(false));
+        let deleted = Rc::new(Cell::new(false));
+        let flag = DropFlag(dropped.clone());
+        let set_deleted = deleted.clone();
+        btn.add_event_handler(Some(Event::Delete), move |_, _, _| {
+            let _ = &flag;
+            set_deleted.set(true);
+        })
+        .unwrap();
+
+        unsafe { lvgl_sys::lv_obj_del(btn.raw().as_ptr()) };
+        assert!(deleted.get());
+        assert!(dropped.get());
+    }
+
+    #[test]
+    fn bubble_events_to_parent() {
+        crate::tests::initialize_test(true);
+        let mut parent = Obj::new().unwrap();
+        let mut child = Btn::create(&mut parent).unwrap();
+        child.add_flag(ObjFlag::EVENT_BUBBLE);
+        assert!(child.has_flag(ObjFlag::EVENT_BUBBLE | ObjFlag::CLICKABLE));
+
+        let child_raw = child.raw();
+        let parent_raw = parent.raw();
+        let bubbled = Rc::new(Cell::new(0));
+        let stop = Rc::new(Cell::new(false));
+        let (parent_clicks, stop_child) = (bubbled.clone(), stop.clone());
+        parent
+            .add_event_handler(Some(Event::Clicked), move |_, _, context| {
+                assert_eq!(context.target().raw(), child_raw);
+                assert_eq!(context.current_target().raw(), parent_raw);
+                parent_clicks.set(parent_clicks.get() + 1);
+            })
+            .unwrap();
+        child
+            .add_event_handler(Some(Event::Clicked), move |_, _, context| {
+                assert_eq!(context.code(), lvgl_sys::lv_event_code_t_LV_EVENT_CLICKED);
+                if stop_child.get() {
+                    context.stop_bubbling();
+                }
+            })
+            .unwrap();
+
+        event_send(&mut child, Event::Clicked).unwrap();
+        assert_eq!(bubbled.get(), 1);
+        stop.set(true);
+        event_send(&mut child, Event::Clicked).unwrap();
+        assert_eq!(bubbled.get(), 1);
+
+        child.clear_flag(ObjFlag::EVENT_BUBBLE);
+        assert!(!child.has_flag(ObjFlag::EVENT_BUBBLE));
+        unsafe { lvgl_sys::lv_obj_del(parent.raw().as_ptr()) };
+    }
+
+    #[test]
+    fn custom_events_with_param() {
+        crate::tests::initialize_test(true);
+        let mut btn = Btn::new().unwrap();
+        let custom = CustomEventId::register();
+        let received = Rc::new(Cell::new(None));
+        let untyped = Rc::new(Cell::new(0));
+        let (typed_params, untyped_params) = (received.clone(), untyped.clone());
+        btn.add_event_handler(Some(Event::Custom(custom)), move |_, event, context| {
+            assert_eq!(event, Event::Custom(custom));
+            assert!(context.param::<i64>().is_none());
+            match context.param::<u32>() {
+                Some(value) => typed_params.set(Some(*value)),
+                None => untyped_params.set(untyped_params.get() + 1),
+            }
+        })
+        .unwrap();
+
+        event_send_with_param(&mut btn, custom, &42u32).unwrap();
+        assert_eq!(received.get(), Some(42));
+        event_send(&mut btn, Event::Custom(custom)).unwrap();
+        event_send_with_param(&mut btn, custom, &-1i16).unwrap();
+        assert_eq!(untyped.get(), 2);
+
+        // Parameters not sent from Rust are not read
+        let mut raw = u64::MAX;
+        unsafe {
+            lvgl_sys::lv_event_send(
+                btn.raw().as_ptr(),
+                custom.code(),
+                &mut raw as *mut u64 as *mut _,
+            )
+        };
+        assert_eq!(untyped.get(), 3);
+        unsafe { lvgl_sys::lv_obj_del(btn.raw().as_ptr()) };
+    }
+}
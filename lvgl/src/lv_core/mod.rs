@@ -1,8 +1,12 @@
 #[macro_use]
 pub mod obj;
+pub mod event;
 pub mod group;
+pub mod owned;
 pub mod screen;
 pub mod style;
 
+pub use event::{EventContext, HandlerId};
 pub use obj::*;
+pub use owned::{Borrowed, Owned};
 pub use screen::*;
@@ -5,8 +5,12 @@
 //! are special in that they do not have a parent object but do still implement
 //! `NativeObject`.
 
+use crate::lv_core::event::{EventContext, HandlerId};
+use crate::lv_core::owned::Owned;
 use crate::lv_core::style::Style;
-use crate::{Align, LvError, LvResult};
+#[cfg(feature = "snapshot")]
+use crate::misc::image::{ColorFormat, ImageBuf};
+use crate::{Align, Event, LvError, LvResult, Screen, SpecialEvent};
 use core::fmt::{self, Debug};
 use core::marker::PhantomData;
 use core::ptr::{self, NonNull};
@@ -69,6 +73,25 @@ impl<'a> Obj<'a> {
             None => Err(LvError::LvOOMemory),
         }
     }
+
+    /// Returns the object as a `W` if it is exactly of that type, e.g. to
+    /// operate on the `Label` inside of a `Btn`. The returned handle does not
+    /// own the object. See `Obj::is` for how types are matched.
+    pub fn downcast<W: Widget<'a> + WidgetClass>(&self) -> Option<W> {
+        if self.is::<W>() {
+            unsafe { W::from_raw(self.raw) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the object is exactly of the type `W`, as checked by
+    /// `lv_obj_check_type`. Classes derived from the class of `W` do not
+    /// match: every widget derives from `Obj`, yet only plain objects are
+    /// `Obj`s.
+    pub fn is<W: WidgetClass>(&self) -> bool {
+        unsafe { lvgl_sys::lv_obj_check_type(self.raw.as_ptr(), W::class()) }
+    }
 }
 
 impl NativeObject for Obj<'_> {
@@ -77,18 +100,32 @@ impl NativeObject for Obj<'_> {
     }
 }
 
+/// Widgets with an LVGL class, which objects can be checked against with
+/// `Obj::is` and `Obj::downcast`.
+pub trait WidgetClass {
+    /// Returns the LVGL class of the widget.
+    fn class() -> &'static lvgl_sys::lv_obj_class_t;
+}
+
+impl WidgetClass for Obj<'_> {
+    fn class() -> &'static lvgl_sys::lv_obj_class_t {
+        unsafe { &lvgl_sys::lv_obj_class }
+    }
+}
+
 /// A wrapper for all LVGL common operations on generic objects.
 pub trait Widget<'a>: NativeObject + Sized + 'a {
-    type SpecialEvent;
+    type SpecialEvent: SpecialEvent;
     type Part: Into<lvgl_sys::lv_part_t>;
 
     /// Construct an instance of the object from a raw pointer.
     ///
     /// # Safety
     ///
-    /// If the pointer is derived from a Rust-instantiated `obj` such as via
-    /// calling `.raw()`, only the `obj` that survives longest may be dropped
-    /// and the caller is responsible for ensuring data races do not occur.
+    /// The pointer must point to a live object of this type. The handle does
+    /// not own the object: it must not be used after the object is deleted,
+    /// e.g. by dropping an `Owned` handle of it, and the caller is
+    /// responsible for ensuring data races do not occur.
     unsafe fn from_raw(raw_pointer: ptr::NonNull<lvgl_sys::lv_obj_t>) -> Option<Self>;
 
     /// Adds a `Style` to a given widget.
@@ -149,10 +186,114 @@ pub trait Widget<'a>: NativeObject + Sized + 'a {
             );
         }
     }
+
+    /// Returns the parent of the widget, or `None` for screens.
+    fn get_parent(&self) -> Option<Obj<'_>> {
+        let raw = unsafe { lvgl_sys::lv_obj_get_parent(self.raw().as_ptr()) };
+        NonNull::new(raw).and_then(|raw| unsafe { Obj::from_raw(raw) })
+    }
+
+    /// Returns the child of the widget at `index`, in the order they were
+    /// created. Negative indices count from the last child, which is -1.
+    fn get_child(&self, index: i32) -> Option<Obj<'_>> {
+        let raw = unsafe { lvgl_sys::lv_obj_get_child(self.raw().as_ptr(), index) };
+        NonNull::new(raw).and_then(|raw| unsafe { Obj::from_raw(raw) })
+    }
+
+    /// Returns the number of children of the widget.
+    fn get_child_cnt(&self) -> u32 {
+        unsafe { lvgl_sys::lv_obj_get_child_cnt(self.raw().as_ptr()) }
+    }
+
+    /// Returns the screen the widget is on.
+    fn get_screen(&self) -> LvResult<Screen<'_>> {
+        let raw = unsafe { lvgl_sys::lv_obj_get_screen(self.raw().as_ptr()) };
+        let obj = NonNull::new(raw).and_then(|raw| unsafe { Obj::from_raw(raw) });
+        Screen::try_from(obj.ok_or(LvError::InvalidReference)?)
+    }
+
+    /// Moves the widget to a new parent, keeping its position relative to
+    /// the parent.
+    fn set_parent(&mut self, parent: &mut impl NativeObject) {
+        unsafe { lvgl_sys::lv_obj_set_parent(self.raw().as_ptr(), parent.raw().as_ptr()) }
+    }
+
+    /// Adds a handler called with the events of the widget matching `filter`,
+    /// or with all of its events if it is `None`. Only the kind of the filter
+    /// event is compared, e.g. `Event::Key(KeypadInputData::Enter)` matches
+    /// every key.
+    ///
+    /// The handler is freed when it is removed with `remove_event_handler`,
+    /// or after it has seen the `Delete` event of the widget. As the object
+    /// outlives handles such as this one, the handler must not borrow
+    /// anything. Events a handler sends to the widget while it is running are
+    /// not passed to itself, only to the other handlers.
+    fn add_event_handler<F>(
+        &mut self,
+        filter: Option<Event<Self::SpecialEvent>>,
+        handler: F,
+    ) -> LvResult<HandlerId>
+    where
+        F: FnMut(Self, Event<Self::SpecialEvent>, &mut EventContext) + 'static,
+    {
+        crate::event::add_event_handler(self, filter, handler)
+    }
+
+    /// Removes and frees an event handler added to the widget. Fails if the
+    /// handler was already removed or belongs to another widget. A handler
+    /// may remove itself, in which case it is freed once it returns.
+    fn remove_event_handler(&mut self, id: HandlerId) -> LvResult<()> {
+        crate::event::remove_event_handler(self, id)
+    }
+
+    /// Returns a stream of the events of the widget, for use in async code.
+    /// Events are dropped while its buffer is full, see
+    /// `EventStream::dropped`, and custom events are streamed without their
+    /// parameter. See the `asynch` module.
+    #[cfg(feature = "async")]
+    fn events(&mut self) -> LvResult<crate::asynch::EventStream<Self::SpecialEvent>>
+    where
+        Self::SpecialEvent: 'static,
+    {
+        crate::asynch::EventStream::new(self)
+    }
+
+    /// Wraps the widget in an `Owned` handle, which deletes its object when
+    /// dropped.
+    fn into_owned(self) -> LvResult<Owned<Self>> {
+        Owned::new(self)
+    }
+
+    /// Sets the given flags of the widget.
+    fn add_flag(&mut self, flags: ObjFlag) {
+        unsafe { lvgl_sys::lv_obj_add_flag(self.raw().as_ptr(), flags.bits()) }
+    }
+
+    /// Clears the given flags of the widget.
+    fn clear_flag(&mut self, flags: ObjFlag) {
+        unsafe { lvgl_sys::lv_obj_clear_flag(self.raw().as_ptr(), flags.bits()) }
+    }
+
+    /// Returns whether all of the given flags of the widget are set.
+    fn has_flag(&self, flags: ObjFlag) -> bool {
+        unsafe { lvgl_sys::lv_obj_has_flag(self.raw().as_ptr(), flags.bits()) }
+    }
+
+    /// Renders the widget and its children into a new image, including
+    /// anything drawn outside of its bounds such as shadows. Requires
+    /// `LV_USE_SNAPSHOT` to be enabled in `lv_conf.h`.
+    #[cfg(feature = "snapshot")]
+    fn snapshot(&self, color_format: ColorFormat) -> LvResult<ImageBuf> {
+        let raw = unsafe { lvgl_sys::lv_snapshot_take(self.raw().as_ptr(), color_format.into()) };
+        match NonNull::new(raw) {
+            Some(raw) => Ok(unsafe { ImageBuf::from_snapshot(raw) }),
+            None => Err(LvError::LvOOMemory),
+        }
+    }
 }
 
 impl<'a> Widget<'a> for Obj<'a> {
-    type SpecialEvent = u32;
+    type SpecialEvent = core::convert::Infallible;
     type Part = Part;
 
     unsafe fn from_raw(raw: NonNull<lvgl_sys::lv_obj_t>) -> Option<Self> {
@@ -165,13 +306,13 @@ impl<'a> Widget<'a> for Obj<'a> {
 
 macro_rules! define_object {
     ($item:ident) => {
-        define_object!($item, event = (), part = $crate::Part);
+        define_object!($item, event = core::convert::Infallible, part = $crate::Part);
     };
     ($item:ident, event = $event_type:ty) => {
         define_object!($item, event = $event_type, part = $crate::Part);
     };
     ($item:ident, part = $part_type:ty) => {
-        define_object!($item, event = (), part = $part_type);
+        define_object!($item, event = core::convert::Infallible, part = $part_type);
     };
     ($item:ident, part = $part_type:ty, event = $event_type:ty) => {
         define_object!($item, event = $event_type, part = $part_type);
@@ -183,23 +324,18 @@ macro_rules! define_object {
         }
 
         impl<'a> $item<'a> {
+            /// Calls `f` with every event of the widget. See
+            /// `Widget::add_event_handler` to handle specific events or to
+            /// remove the handler later.
             pub fn on_event<F>(&mut self, f: F) -> $crate::LvResult<()>
             where
-                F: FnMut(Self, $crate::support::Event<<Self as $crate::Widget<'a>>::SpecialEvent>),
+                F: FnMut(
+                        Self,
+                        $crate::support::Event<<Self as $crate::Widget<'a>>::SpecialEvent>,
+                        &mut $crate::EventContext,
+                    ) + 'static,
             {
-                use $crate::NativeObject;
-                unsafe {
-                    let obj = self.raw().as_mut();
-                    obj.user_data = $crate::Box::into_raw($crate::Box::new(f)) as *mut _;
-                    lvgl_sys::lv_obj_add_event_cb(
-                        obj,
-                        lvgl_sys::lv_event_cb_t::Some(
-                            $crate::support::event_callback::<'a, Self, F>,
-                        ),
-                        lvgl_sys::lv_event_code_t_LV_EVENT_ALL,
-                        obj.user_data,
-                    );
-                }
+                $crate::Widget::add_event_handler(self, None, f)?;
                 Ok(())
             }
         }
@@ -270,6 +406,55 @@ pub enum Part {
     Any,
 }
 
+bitflags! {
+    /// Flags changing the behavior of an object.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct ObjFlag: lvgl_sys::lv_obj_flag_t {
+        /// Makes the object hidden, as if it was not there at all.
+        const HIDDEN = lvgl_sys::LV_OBJ_FLAG_HIDDEN;
+        /// Makes the object clickable by input devices.
+        const CLICKABLE = lvgl_sys::LV_OBJ_FLAG_CLICKABLE;
+        /// Adds the focused state to the object when clicked.
+        const CLICK_FOCUSABLE = lvgl_sys::LV_OBJ_FLAG_CLICK_FOCUSABLE;
+        /// Toggles the checked state of the object when clicked.
+        const CHECKABLE = lvgl_sys::LV_OBJ_FLAG_CHECKABLE;
+        /// Makes the object scrollable.
+        const SCROLLABLE = lvgl_sys::LV_OBJ_FLAG_SCROLLABLE;
+        /// Allows scrolling inside the object, but slower.
+        const SCROLL_ELASTIC = lvgl_sys::LV_OBJ_FLAG_SCROLL_ELASTIC;
+        /// Keeps scrolling for a while after the object was thrown.
+        const SCROLL_MOMENTUM = lvgl_sys::LV_OBJ_FLAG_SCROLL_MOMENTUM;
+        /// Scrolls only one snappable child at a time.
+        const SCROLL_ONE = lvgl_sys::LV_OBJ_FLAG_SCROLL_ONE;
+        /// Propagates horizontal scrolling to the parent.
+        const SCROLL_CHAIN_HOR = lvgl_sys::LV_OBJ_FLAG_SCROLL_CHAIN_HOR;
+        /// Propagates vertical scrolling to the parent.
+        const SCROLL_CHAIN_VER = lvgl_sys::LV_OBJ_FLAG_SCROLL_CHAIN_VER;
+        /// Scrolls the object into view when it is focused.
+        const SCROLL_ON_FOCUS = lvgl_sys::LV_OBJ_FLAG_SCROLL_ON_FOCUS;
+        /// Allows scrolling the focused object with arrow keys.
+        const SCROLL_WITH_ARROW = lvgl_sys::LV_OBJ_FLAG_SCROLL_WITH_ARROW;
+        /// Allows snapping the object when its parent scrolls.
+        const SNAPPABLE = lvgl_sys::LV_OBJ_FLAG_SNAPPABLE;
+        /// Keeps the object pressed when the press slides off of it.
+        const PRESS_LOCK = lvgl_sys::LV_OBJ_FLAG_PRESS_LOCK;
+        /// Propagates events to the parent.
+        const EVENT_BUBBLE = lvgl_sys::LV_OBJ_FLAG_EVENT_BUBBLE;
+        /// Propagates gestures to the parent.
+        const GESTURE_BUBBLE = lvgl_sys::LV_OBJ_FLAG_GESTURE_BUBBLE;
+        /// Allows hit-testing with `Event::HitTest`, e.g. for rounded corners.
+        const ADV_HITTEST = lvgl_sys::LV_OBJ_FLAG_ADV_HITTEST;
+        /// Makes the object ignored by the layout of its parent.
+        const IGNORE_LAYOUT = lvgl_sys::LV_OBJ_FLAG_IGNORE_LAYOUT;
+        /// Keeps the object from scrolling with its parent and ignores it in
+        /// layouts.
+        const FLOATING = lvgl_sys::LV_OBJ_FLAG_FLOATING;
+        /// Keeps the children of the object from being clipped to its
+        /// boundary.
+        const OVERFLOW_VISIBLE = lvgl_sys::LV_OBJ_FLAG_OVERFLOW_VISIBLE;
+    }
+}
+
 impl Default for Part {
     fn default() -> Self {
         Self::Main
@@ -292,3 +477,35 @@ impl From<Part> for lvgl_sys::lv_part_t {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::widgets::{Btn, Label};
+
+    #[test]
+    fn walk_object_tree() {
+        crate::tests::initialize_test(true);
+        let mut btn = Btn::new().unwrap();
+        let label = Label::create(&mut btn).unwrap();
+        assert_eq!(btn.get_child_cnt(), 1);
+        assert!(btn.get_child(1).is_none());
+
+        let child = btn.get_child(-1).unwrap();
+        assert_eq!(child.raw(), label.raw());
+        assert_eq!(child.get_parent().unwrap().raw(), btn.raw());
+        assert!(child.is::<Label>());
+        assert!(!child.is::<Obj>());
+        assert!(child.downcast::<Btn>().is_none());
+        let mut label = child.downcast::<Label>().unwrap();
+
+        let screen = btn.get_screen().unwrap();
+        assert_eq!(btn.get_parent().unwrap().raw(), screen.raw());
+        assert!(screen.get_parent().is_none());
+
+        let mut other = Obj::new().unwrap();
+        label.set_parent(&mut other);
+        assert_eq!(btn.get_child_cnt(), 0);
+        assert_eq!(other.get_child(0).unwrap().raw(), label.raw());
+    }
+}
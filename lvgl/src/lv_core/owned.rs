@@ -0,0 +1,345 @@
+//! Owning handles of objects.
+//!
+//! Widgets such as `Btn` are plain handles to LVGL objects: more of them can
+//! be made with `Widget::from_raw`, and none of them ever delete the object.
+//! `Owned` wraps a widget to delete its object, along with its children and
+//! event handlers, when the handle is dropped:
+//!
+//! ```ignore
+//! let mut popup = Obj::new()?.into_owned()?;
+//! let mut label = Label::create(&mut popup)?;
+//! label.set_text(cstr!("Saved"))?;
+//! // ...
+//! drop(popup); // deletes the popup and its label
+//! ```
+//!
+//! Objects can also be deleted by LVGL, e.g. together with their parent.
+//! `Owned` tracks this with the `Delete` event of the object and does not
+//! delete it again. `Owned::is_alive` tells whether the object still exists,
+//! and dereferencing the handle of a deleted object panics instead of
+//! accessing freed memory. Objects are also considered deleted once LVGL is
+//! deinitialized with `deinit`.
+//!
+//! Handles of the children of an owned object, such as `label` above, can be
+//! tracked the same way with `Owned::track`, which returns a `Borrowed`
+//! handle. It never deletes the object, and cannot outlive the `Owned`
+//! handle it was borrowed from:
+//!
+//! ```ignore
+//! let label = popup.track(Label::create(&mut popup)?)?;
+//! drop(popup);
+//! assert!(!label.is_alive());
+//! ```
+//!
+//! Plain handles, e.g. those made with `Widget::from_raw` or passed to event
+//! handlers, are not tracked and must not be used after their object is
+//! deleted.
+
+use crate::lv_core::event::{self, HandlerId};
+use crate::{Box, Event, LvError, LvResult, NativeObject, Widget};
+use core::cell::Cell;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
+
+/// Tracks whether an object still exists with its `Delete` event.
+struct Tracker {
+    obj: NonNull<lvgl_sys::lv_obj_t>,
+    // Freed by LVGL if it is deinitialized first
+    alive: ManuallyDrop<Box<Cell<bool>>>,
+    handler: HandlerId,
+    generation: usize,
+}
+
+impl Tracker {
+    fn new<'a, W: Widget<'a>>(widget: &mut W) -> LvResult<Self> {
+        let alive = Box::new(Cell::new(true));
+        let flag: *const Cell<bool> = &*alive;
+        // The flag outlives the handler: the handler is removed before the
+        // flag is dropped, unless LVGL already freed it with the object
+        let handler = widget.add_event_handler(Some(Event::Delete), move |_, _, _| unsafe {
+            (*flag).set(false)
+        })?;
+        Ok(Self {
+            obj: widget.raw(),
+            alive: ManuallyDrop::new(alive),
+            handler,
+            generation: crate::generation(),
+        })
+    }
+
+    fn is_alive(&self) -> bool {
+        self.generation == crate::generation() && self.alive.get()
+    }
+}
+
+impl Drop for Tracker {
+    fn drop(&mut self) {
+        if self.generation != crate::generation() {
+            return;
+        }
+        if self.alive.get() {
+            // The handler only fails to be removed if LVGL already freed it
+            let obj = unsafe { crate::Obj::from_raw(self.obj) }.unwrap();
+            let _ = event::remove_event_handler(&obj, self.handler);
+        }
+        unsafe { ManuallyDrop::drop(&mut self.alive) }
+    }
+}
+
+/// A widget handle which deletes its object when dropped.
+pub struct Owned<W: NativeObject> {
+    widget: W,
+    tracker: Tracker,
+}
+
+impl<'a, W: Widget<'a>> Owned<W> {
+    /// Takes ownership of the object of `widget`. Other handles of the object
+    /// must not be used after the `Owned` handle is dropped.
+    pub fn new(mut widget: W) -> LvResult<Self> {
+        let tracker = Tracker::new(&mut widget)?;
+        Ok(Self { widget, tracker })
+    }
+}
+
+impl<W: NativeObject> Owned<W> {
+    /// Returns whether the object still exists.
+    pub fn is_alive(&self) -> bool {
+        self.tracker.is_alive()
+    }
+
+    /// Returns the widget, or `None` if its object was deleted.
+    pub fn get(&self) -> Option<&W> {
+        self.is_alive().then_some(&self.widget)
+    }
+
+    /// Returns the widget, or `None` if its object was deleted.
+    pub fn get_mut(&mut self) -> Option<&mut W> {
+        self.is_alive().then_some(&mut self.widget)
+    }
+
+    /// Tracks the handle of a descendant of the object, such as a child
+    /// created with it as the parent. Fails if `widget` is not a descendant.
+    pub fn track<'c, C: Widget<'c>>(&self, mut widget: C) -> LvResult<Borrowed<'_, C>> {
+        let owner = self.deref().raw().as_ptr();
+        let mut parent = unsafe { lvgl_sys::lv_obj_get_parent(widget.raw().as_ptr()) };
+        while parent != owner {
+            if parent.is_null() {
+                return Err(LvError::InvalidReference);
+            }
+            parent = unsafe { lvgl_sys::lv_obj_get_parent(parent) };
+        }
+        let tracker = Tracker::new(&mut widget)?;
+        Ok(Borrowed {
+            widget,
+            tracker,
+            _owner: PhantomData,
+        })
+    }
+
+    /// Gives up ownership of the object without deleting it, returning the
+    /// plain widget handle, or `None` if the object was deleted.
+    pub fn release(self) -> Option<W> {
+        let this = ManuallyDrop::new(self);
+        // Safety: `this` is never used or dropped again.
+        let (widget, tracker) = unsafe { (ptr::read(&this.widget), ptr::read(&this.tracker)) };
+        // Dropping the tracker removes its handler
+        tracker.is_alive().then_some(widget)
+    }
+
+    /// Deletes the object once LVGL is done with the current event. Use this
+    /// instead of dropping the handle to delete an object from one of its own
+    /// event handlers.
+    pub fn delete_async(self) {
+        if let Some(widget) = self.release() {
+            unsafe { lvgl_sys::lv_obj_del_async(widget.raw().as_ptr()) }
+        }
+    }
+}
+
+impl<W: NativeObject> Drop for Owned<W> {
+    fn drop(&mut self) {
+        if self.is_alive() {
+            // Frees the event handlers of the object, and clears the flag of
+            // the tracker
+            unsafe { lvgl_sys::lv_obj_del(self.widget.raw().as_ptr()) }
+        }
+    }
+}
+
+impl<W: NativeObject> Deref for Owned<W> {
+    type Target = W;
+
+    fn deref(&self) -> &W {
+        self.get().expect("use of a deleted object")
+    }
+}
+
+impl<W: NativeObject> DerefMut for Owned<W> {
+    fn deref_mut(&mut self) -> &mut W {
+        self.get_mut().expect("use of a deleted object")
+    }
+}
+
+impl<W: NativeObject> NativeObject for Owned<W> {
+    fn raw(&self) -> NonNull<lvgl_sys::lv_obj_t> {
+        self.deref().raw()
+    }
+}
+
+impl<W: NativeObject + Debug> Debug for Owned<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Owned")
+            .field("widget", &self.get())
+            .finish()
+    }
+}
+
+/// A handle of a descendant of an owned object, returned by `Owned::track`.
+/// It does not delete the object, but tracks whether it still exists like
+/// `Owned`.
+pub struct Borrowed<'o, W: NativeObject> {
+    widget: W,
+    tracker: Tracker,
+    _owner: PhantomData<&'o ()>,
+}
+
+impl<W: NativeObject> Borrowed<'_, W> {
+    /// Returns whether the object still exists.
+    pub fn is_alive(&self) -> bool {
+        self.tracker.is_alive()
+    }
+
+    /// Returns the widget, or `None` if its object was deleted.
+    pub fn get(&self) -> Option<&W> {
+        self.is_alive().then_some(&self.widget)
+    }
+
+    /// Returns the widget, or `None` if its object was deleted.
+    pub fn get_mut(&mut self) -> Option<&mut W> {
+        self.is_alive().then_some(&mut self.widget)
+    }
+}
+
+impl<W: NativeObject> Deref for Borrowed<'_, W> {
+    type Target = W;
+
+    fn deref(&self) -> &W {
+        self.get().expect("use of a deleted object")
+    }
+}
+
+impl<W: NativeObject> DerefMut for Borrowed<'_, W> {
+    fn deref_mut(&mut self) -> &mut W {
+        self.get_mut().expect("use of a deleted object")
+    }
+}
+
+impl<W: NativeObject> NativeObject for Borrowed<'_, W> {
+    fn raw(&self) -> NonNull<lvgl_sys::lv_obj_t> {
+        self.deref().raw()
+    }
+}
+
+impl<W: NativeObject + Debug> Debug for Borrowed<'_, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Borrowed")
+            .field("widget", &self.get())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::widgets::{Btn, Label};
+    use crate::{NativeObject, Obj, Widget};
+
+    fn is_valid(raw: core::ptr::NonNull<lvgl_sys::lv_obj_t>) -> bool {
+        unsafe { lvgl_sys::lv_obj_is_valid(raw.as_ptr()) }
+    }
+
+    #[test]
+    fn delete_owned_on_drop() {
+        crate::tests::initialize_test(true);
+        let btn = Btn::new().unwrap().into_owned().unwrap();
+        let raw = btn.raw();
+        assert!(btn.is_alive());
+        assert!(is_valid(raw));
+        drop(btn);
+        assert!(!is_valid(raw));
+    }
+
+    #[test]
+    fn track_deleted_children() {
+        crate::tests::initialize_test(true);
+        let mut parent = Obj::new().unwrap().into_owned().unwrap();
+        let mut child = Btn::create(&mut parent).unwrap().into_owned().unwrap();
+        let label = Label::create(&mut child).unwrap();
+        let label_raw = label.raw();
+
+        drop(parent);
+        assert!(!child.is_alive());
+        assert!(child.get().is_none());
+        assert!(!is_valid(label_raw));
+        // Dropping the child does not delete it twice
+        drop(child);
+    }
+
+    #[test]
+    fn track_borrowed_children() {
+        crate::tests::initialize_test(true);
+        let mut parent = Obj::new().unwrap().into_owned().unwrap();
+        let mut child = Btn::create(&mut parent).unwrap();
+        let grandchild = Label::create(&mut child).unwrap();
+        let mut child = parent.track(child).unwrap();
+        let grandchild = parent.track(grandchild).unwrap();
+        assert!(child.is_alive());
+        child.add_flag(crate::ObjFlag::HIDDEN);
+
+        unsafe { lvgl_sys::lv_obj_del(child.raw().as_ptr()) };
+        assert!(!child.is_alive());
+        assert!(!grandchild.is_alive());
+        assert!(child.get().is_none());
+        drop(child);
+        drop(grandchild);
+        assert!(parent.is_alive());
+    }
+
+    #[test]
+    fn track_only_descendants() {
+        crate::tests::initialize_test(true);
+        let mut parent = Obj::new().unwrap().into_owned().unwrap();
+        let other = Btn::new().unwrap();
+        let other_raw = other.raw();
+        assert!(parent.track(other).is_err());
+
+        // Dropping a borrowed handle does not delete the object
+        let child = Btn::create(&mut parent).unwrap();
+        let child_raw = child.raw();
+        drop(parent.track(child).unwrap());
+        assert!(is_valid(child_raw));
+        unsafe { lvgl_sys::lv_obj_del(other_raw.as_ptr()) };
+    }
+
+    #[test]
+    fn owned_after_deinit() {
+        crate::tests::initialize_test(true);
+        let btn = Btn::new().unwrap().into_owned().unwrap();
+        crate::tests::initialize_test(true);
+        assert!(!btn.is_alive());
+        // Does not delete the object or free its flag a second time
+        drop(btn);
+    }
+
+    #[test]
+    fn release_owned_object() {
+        crate::tests::initialize_test(true);
+        let btn = Btn::new().unwrap().into_owned().unwrap();
+        let raw = btn.raw();
+        let btn = btn.release().unwrap();
+        assert!(is_valid(raw));
+        unsafe { lvgl_sys::lv_obj_del(btn.raw().as_ptr()) };
+    }
+}
@@ -1,14 +1,23 @@
-use crate::{LvError, LvResult, NativeObject, Obj, Part, Widget};
+use crate::{Display, LvError, LvResult, NativeObject, Obj, Part, Widget};
+use core::ptr::NonNull;
 
-/// An LVGL screen.
+/// An LVGL screen, bound to the display it is shown on.
 #[derive(Debug)]
 pub struct Screen<'a> {
     raw: Obj<'a>,
+    disp: NonNull<lvgl_sys::lv_disp_t>,
 }
 
 impl Screen<'_> {
+    /// Creates a new screen on the default display. Use
+    /// `Display::create_screen` to create a screen on a specific display.
     pub fn blank() -> LvResult<Self> {
-        Ok(Self { raw: Obj::blank()? })
+        Self::try_from(Obj::blank()?)
+    }
+
+    /// Returns the display this screen belongs to.
+    pub fn display(&self) -> Display {
+        Display::from_raw(self.disp, None)
     }
 }
 
@@ -19,7 +28,7 @@ impl NativeObject for Screen<'_> {
 }
 
 impl<'a> Widget<'a> for Screen<'a> {
-    type SpecialEvent = u32;
+    type SpecialEvent = core::convert::Infallible;
     type Part = Part;
 
     unsafe fn from_raw(raw: core::ptr::NonNull<lvgl_sys::lv_obj_t>) -> Option<Self> {
@@ -35,7 +44,13 @@ impl<'a> TryFrom<Obj<'a>> for Screen<'a> {
 
     fn try_from(value: Obj<'a>) -> Result<Self, Self::Error> {
         match unsafe { value.raw().as_mut().parent } as usize {
-            0 => Ok(Self { raw: value }),
+            0 => {
+                let disp = unsafe { lvgl_sys::lv_obj_get_disp(value.raw().as_ptr()) };
+                Ok(Self {
+                    raw: value,
+                    disp: NonNull::new(disp).ok_or(LvError::InvalidReference)?,
+                })
+            }
             _ => Err(LvError::InvalidReference),
         }
     }
@@ -115,7 +115,7 @@ where
         // yes, we have to do it this way. Casting `obj` directly to `&mut Obj` segfaults
         let obj = (*(obj as *mut T)).raw();
         if !anim.as_ref().user_data.is_null() {
-            let callback = &mut *(obj.as_ref().user_data as *mut F);
+            let callback = &mut *(anim.as_ref().user_data as *mut F);
             let mut obj_nondrop = Obj::from_raw(obj).unwrap();
             callback(&mut obj_nondrop, val);
             mem::forget(obj_nondrop)
@@ -139,4 +139,21 @@ mod test {
             Animation::new(&mut btn, Duration::from_millis(10), 0, 100, |_, _| {}).unwrap();
         anim.start();
     }
+
+    #[test]
+    fn anim_calls_animator() {
+        crate::tests::initialize_test(true);
+        let mut btn = Btn::new().unwrap();
+        let last = core::cell::Cell::new(None);
+        let mut anim = Animation::new(&mut btn, Duration::from_millis(10), 0, 100, |_, val| {
+            last.set(Some(val))
+        })
+        .unwrap();
+        anim.start();
+        unsafe {
+            lvgl_sys::lv_tick_inc(50);
+            lvgl_sys::lv_timer_handler();
+        }
+        assert_eq!(last.get(), Some(100));
+    }
 }
@@ -0,0 +1,154 @@
+//! Image buffers and color formats.
+
+#[cfg(feature = "snapshot")]
+use core::ptr::NonNull;
+#[cfg(feature = "snapshot")]
+use core::slice;
+
+/// Pixel format of an image.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ColorFormat {
+    /// Colors in the native format of `LV_COLOR_DEPTH`.
+    TrueColor,
+    /// Native colors followed by an 8-bit alpha value per pixel.
+    TrueColorAlpha,
+    /// Native colors where `LV_COLOR_CHROMA_KEY` is transparent.
+    TrueColorChromaKeyed,
+    /// 1-bit alpha values only.
+    Alpha1Bit,
+    /// 2-bit alpha values only.
+    Alpha2Bit,
+    /// 4-bit alpha values only.
+    Alpha4Bit,
+    /// 8-bit alpha values only.
+    Alpha8Bit,
+}
+
+impl From<ColorFormat> for lvgl_sys::lv_img_cf_t {
+    fn from(value: ColorFormat) -> Self {
+        (match value {
+            ColorFormat::TrueColor => lvgl_sys::LV_IMG_CF_TRUE_COLOR,
+            ColorFormat::TrueColorAlpha => lvgl_sys::LV_IMG_CF_TRUE_COLOR_ALPHA,
+            ColorFormat::TrueColorChromaKeyed => lvgl_sys::LV_IMG_CF_TRUE_COLOR_CHROMA_KEYED,
+            ColorFormat::Alpha1Bit => lvgl_sys::LV_IMG_CF_ALPHA_1BIT,
+            ColorFormat::Alpha2Bit => lvgl_sys::LV_IMG_CF_ALPHA_2BIT,
+            ColorFormat::Alpha4Bit => lvgl_sys::LV_IMG_CF_ALPHA_4BIT,
+            ColorFormat::Alpha8Bit => lvgl_sys::LV_IMG_CF_ALPHA_8BIT,
+        }) as lvgl_sys::lv_img_cf_t
+    }
+}
+
+impl TryFrom<lvgl_sys::lv_img_cf_t> for ColorFormat {
+    type Error = ();
+
+    fn try_from(value: lvgl_sys::lv_img_cf_t) -> Result<Self, Self::Error> {
+        match value as u32 {
+            lvgl_sys::LV_IMG_CF_TRUE_COLOR => Ok(ColorFormat::TrueColor),
+            lvgl_sys::LV_IMG_CF_TRUE_COLOR_ALPHA => Ok(ColorFormat::TrueColorAlpha),
+            lvgl_sys::LV_IMG_CF_TRUE_COLOR_CHROMA_KEYED => Ok(ColorFormat::TrueColorChromaKeyed),
+            lvgl_sys::LV_IMG_CF_ALPHA_1BIT => Ok(ColorFormat::Alpha1Bit),
+            lvgl_sys::LV_IMG_CF_ALPHA_2BIT => Ok(ColorFormat::Alpha2Bit),
+            lvgl_sys::LV_IMG_CF_ALPHA_4BIT => Ok(ColorFormat::Alpha4Bit),
+            lvgl_sys::LV_IMG_CF_ALPHA_8BIT => Ok(ColorFormat::Alpha8Bit),
+            _ => Err(()),
+        }
+    }
+}
+
+/// An image owned by Rust, such as a snapshot taken with `Widget::snapshot`.
+/// The image data is allocated by LVGL and freed when this is dropped.
+///
+/// An `ImageBuf` can be displayed with `Img::set_src`, which takes ownership
+/// of it.
+#[cfg(feature = "snapshot")]
+pub struct ImageBuf {
+    raw: NonNull<lvgl_sys::lv_img_dsc_t>,
+}
+
+#[cfg(feature = "snapshot")]
+impl ImageBuf {
+    /// Takes ownership of an image descriptor returned by `lv_snapshot_take`.
+    ///
+    /// # Safety
+    ///
+    /// The descriptor and its data must have been allocated by
+    /// `lv_snapshot_take` and must not be freed by anything else.
+    pub unsafe fn from_snapshot(raw: NonNull<lvgl_sys::lv_img_dsc_t>) -> Self {
+        Self { raw }
+    }
+
+    /// Returns the width of the image in pixels.
+    pub fn width(&self) -> u32 {
+        self.header().w()
+    }
+
+    /// Returns the height of the image in pixels.
+    pub fn height(&self) -> u32 {
+        self.header().h()
+    }
+
+    /// Returns the color format of the image, or `None` if it is not one of
+    /// the formats in `ColorFormat`.
+    pub fn color_format(&self) -> Option<ColorFormat> {
+        ColorFormat::try_from(self.header().cf() as lvgl_sys::lv_img_cf_t).ok()
+    }
+
+    /// Returns the raw pixel data of the image, in the image's color format.
+    pub fn data(&self) -> &[u8] {
+        let dsc = self.raw();
+        if dsc.data.is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(dsc.data, dsc.data_size as usize) }
+        }
+    }
+
+    /// Returns the underlying image descriptor.
+    pub fn raw(&self) -> &lvgl_sys::lv_img_dsc_t {
+        unsafe { self.raw.as_ref() }
+    }
+
+    fn header(&self) -> &lvgl_sys::lv_img_header_t {
+        &self.raw().header
+    }
+}
+
+#[cfg(feature = "snapshot")]
+impl core::fmt::Debug for ImageBuf {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ImageBuf")
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("color_format", &self.color_format())
+            .finish()
+    }
+}
+
+#[cfg(feature = "snapshot")]
+impl Drop for ImageBuf {
+    fn drop(&mut self) {
+        unsafe {
+            // LVGL may have cached the image by its address
+            lvgl_sys::lv_img_cache_invalidate_src(self.raw.as_ptr() as *const _);
+            lvgl_sys::lv_snapshot_free(self.raw.as_ptr());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_format_round_trip() {
+        for format in [
+            ColorFormat::TrueColor,
+            ColorFormat::TrueColorAlpha,
+            ColorFormat::Alpha8Bit,
+        ] {
+            let raw: lvgl_sys::lv_img_cf_t = format.into();
+            assert_eq!(ColorFormat::try_from(raw), Ok(format));
+        }
+        assert!(ColorFormat::try_from(lvgl_sys::LV_IMG_CF_RAW as lvgl_sys::lv_img_cf_t).is_err());
+    }
+}
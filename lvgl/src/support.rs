@@ -1,6 +1,6 @@
 use crate::display::DisplayError;
-use crate::Widget;
-use core::convert::{TryFrom, TryInto};
+use crate::input_device::keypad::KeypadInputData;
+use core::convert::{Infallible, TryFrom, TryInto};
 #[cfg(feature = "nightly")]
 use core::error::Error;
 use core::fmt;
@@ -62,6 +62,7 @@ impl From<LvError> for DisplayError {
 
 /// An LVGL color. Equivalent to `lv_color_t`.
 #[derive(Copy, Clone, Default)]
+#[repr(transparent)]
 pub struct Color {
     pub(crate) raw: lvgl_sys::lv_color_t,
 }
@@ -88,6 +89,22 @@ impl Color {
     pub fn b(&self) -> u8 {
         unsafe { lvgl_sys::_LV_COLOR_GET_B(self.raw) as u8 }
     }
+    /// Returns the red, green, and blue values, scaled to 8 bits per channel
+    /// regardless of the configured color depth.
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        let (r_max, g_max, b_max) = match lvgl_sys::LV_COLOR_DEPTH {
+            1 => (1, 1, 1),
+            8 => (7, 7, 3),
+            16 => (31, 63, 31),
+            _ => (255, 255, 255),
+        };
+        let scale = |value: u8, max: u16| (value as u16 * 255 / max) as u8;
+        (
+            scale(self.r(), r_max),
+            scale(self.g(), g_max),
+            scale(self.b(), b_max),
+        )
+    }
 }
 
 #[cfg(feature = "embedded_graphics")]
@@ -116,6 +133,14 @@ impl From<Color> for Rgb565 {
     }
 }
 
+#[cfg(feature = "embedded_graphics")]
+impl From<Rgb888> for Color {
+    fn from(color: Rgb888) -> Self {
+        use embedded_graphics::pixelcolor::RgbColor;
+        Color::from_rgb((color.r(), color.g(), color.b()))
+    }
+}
+
 impl From<Color> for lvgl_sys::lv_color_t {
     fn from(val: Color) -> Self {
         val.raw
@@ -144,9 +169,6 @@ pub enum Event<T> {
     /// Released before `long_press_time` config time. Not called if dragged.
     ShortClicked,
 
-    /// Called on release if not dragged (regardless to long press)
-    Clicked,
-
     /// Pressing for `long_press_time` config time. Not called if dragged.
     LongPressed,
 
@@ -154,145 +176,397 @@ pub enum Event<T> {
     /// called if dragged.
     LongPressedRepeat,
 
+    /// Called on release if not dragged (regardless to long press)
+    Clicked,
+
     /// Called in every case when the object has been released even if it was dragged. Not called
     /// if slid from the object while pressing and released outside of the object. In this
     /// case, `Event<_>::PressLost` is sent.
     Released,
 
-    /// Called when an underlying value is changed e.g. position of a `Slider`.
-    ValueChanged,
+    /// Scrolling begins
+    ScrollBegin,
 
-    ///
-    DrawMain,
+    /// Scrolling ends
+    ScrollEnd,
 
-    ///
-    DrawMainBegin,
+    /// The object is being scrolled
+    Scroll,
 
-    ///
-    DrawMainEnd,
+    /// A gesture was detected in the given direction. Only sent by LVGL while
+    /// it reads an input device
+    Gesture(Direction),
 
-    ///
-    DrawPartBegin,
+    /// A key was sent to the object, e.g. by a keypad or an encoder
+    Key(KeypadInputData),
 
-    ///
-    DrawPartEnd,
+    /// Called on focus
+    Focused,
 
-    ///
-    DrawPost,
+    /// Called when the object loses focus
+    Defocused,
 
-    ///
+    /// The object is defocused but still selected
+    Leave,
+
+    /// Checks whether a point is on the object, to do custom hit-testing
+    HitTest,
+
+    /// Checks whether the object fully covers an area
+    CoverCheck,
+
+    /// Gets the extra area the object needs to be drawn, e.g. for a shadow
+    RefrExtDrawSize,
+
+    /// Starting the main drawing phase
+    DrawMainBegin,
+
+    /// Performing the main drawing
+    DrawMain,
+
+    /// Finishing the main drawing phase
+    DrawMainEnd,
+
+    /// Starting the post draw phase, when all children are drawn
     DrawPostBegin,
 
-    ///
+    /// Performing the post draw phase
+    DrawPost,
+
+    /// Finishing the post draw phase
     DrawPostEnd,
 
-    /// Called on focus
-    Focused,
+    /// Starting to draw a part of the object, which can still be modified
+    DrawPartBegin(DrawPart),
+
+    /// Finishing to draw a part of the object
+    DrawPartEnd(DrawPart),
+
+    /// Called when an underlying value is changed e.g. position of a `Slider`.
+    ValueChanged,
+
+    /// A text is being inserted into the object
+    Insert,
+
+    /// Notifies the object to refresh something on it
+    Refresh,
+
+    /// A process has finished
+    Ready,
 
-    /// Pointer-like input devices events (E.g. mouse or touchpad)
-    Pointer(PointerEvent),
+    /// A process has been cancelled
+    Cancel,
+
+    /// The object is being deleted
+    Delete,
+
+    /// A child was removed, added, or its size or position changed
+    ChildChanged,
+
+    /// A child was created
+    ChildCreated,
+
+    /// A child was deleted
+    ChildDeleted,
+
+    /// A screen unload started, sent immediately when the next screen is loaded
+    ScreenUnloadStart,
+
+    /// A screen load started, sent when the screen change delay has expired
+    ScreenLoadStart,
+
+    /// A screen was loaded
+    ScreenLoaded,
+
+    /// A screen was unloaded
+    ScreenUnloaded,
+
+    /// The coordinates or size of the object have changed
+    SizeChanged,
+
+    /// The style of the object has changed
+    StyleChanged,
+
+    /// The children of the object were repositioned by a layout
+    LayoutChanged,
+
+    /// Gets the internal size of a widget
+    GetSelfSize,
+
+    /// An application-defined event registered with `CustomEventId::register`
+    Custom(CustomEventId),
 
     /// Special event for the object type
     Special(T),
 }
 
+/// The code of an application-defined event, allocated by LVGL. Custom
+/// events are sent with `event_send`, or with a typed parameter with
+/// `event_send_with_param`, and received as `Event::Custom`.
+///
+/// ```ignore
+/// let refresh = CustomEventId::register();
+/// label.add_event_handler(Some(Event::Custom(refresh)), |_, _, context| {
+///     if let Some(reading) = context.param::<SensorReading>() {
+///         // ...
+///     }
+/// })?;
+/// event_send_with_param(&mut label, refresh, &SensorReading { celsius: 21 })?;
+/// ```
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct CustomEventId(lvgl_sys::lv_event_code_t);
+
+impl CustomEventId {
+    /// Registers a new event code. Register every custom event once, e.g. in
+    /// a `static`; codes are never released.
+    pub fn register() -> Self {
+        Self(unsafe { lvgl_sys::lv_event_register_id() })
+    }
+
+    /// Returns the raw event code.
+    pub fn code(&self) -> lvgl_sys::lv_event_code_t {
+        self.0
+    }
+
+    /// Returns the custom event with the given raw code, if it is not one of
+    /// the codes of LVGL.
+    pub fn from_code(code: lvgl_sys::lv_event_code_t) -> Option<Self> {
+        (lvgl_sys::lv_event_code_t__LV_EVENT_LAST..lvgl_sys::lv_event_code_t_LV_EVENT_PREPROCESS)
+            .contains(&code)
+            .then_some(Self(code))
+    }
+}
+
+impl<S: SpecialEvent> Event<S> {
+    /// Converts an event received by an LVGL event callback, reading its
+    /// parameter if there is one. Special events of the widget take
+    /// precedence over generic events with the same code. Returns `None` for
+    /// events which do not have a Rust equivalent.
+    ///
+    /// # Safety
+    ///
+    /// `event` must be a valid pointer to an event being sent by LVGL to a
+    /// widget with the special events `S`.
+    pub unsafe fn from_raw(event: *mut lvgl_sys::lv_event_t) -> Option<Self> {
+        if let Some(special) = S::from_raw(event) {
+            return Some(Event::Special(special));
+        }
+        let code = lvgl_sys::lv_event_get_code(event);
+        match code {
+            lvgl_sys::lv_event_code_t_LV_EVENT_KEY => {
+                let key = lvgl_sys::lv_event_get_key(event);
+                KeypadInputData::try_from(key).ok().map(Event::Key)
+            }
+            lvgl_sys::lv_event_code_t_LV_EVENT_GESTURE => {
+                let indev = lvgl_sys::lv_indev_get_act();
+                if indev.is_null() {
+                    return None;
+                }
+                let dir = lvgl_sys::lv_indev_get_gesture_dir(indev);
+                Some(Event::Gesture(Direction::from_bits_truncate(dir as u8)))
+            }
+            lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_PART_BEGIN => {
+                DrawPart::from_raw(lvgl_sys::lv_event_get_draw_part_dsc(event))
+                    .map(Event::DrawPartBegin)
+            }
+            lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_PART_END => {
+                DrawPart::from_raw(lvgl_sys::lv_event_get_draw_part_dsc(event))
+                    .map(Event::DrawPartEnd)
+            }
+            _ => code.try_into().ok(),
+        }
+    }
+}
+
+/// Converts an event code. Events with a parameter, i.e. `Gesture`, `Key`,
+/// `DrawPartBegin` and `DrawPartEnd`, cannot be converted from their code
+/// alone; use `Event::from_raw` for these.
 impl<S> TryFrom<lvgl_sys::lv_event_code_t> for Event<S> {
     type Error = ();
 
     fn try_from(value: lvgl_sys::lv_event_code_t) -> Result<Self, Self::Error> {
-        const LV_EVENT_PRESSED: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_PRESSED;
-        const LV_EVENT_PRESSING: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_PRESSING;
-        const LV_EVENT_PRESS_LOST: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_PRESS_LOST;
-        const LV_EVENT_SHORT_CLICKED: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_SHORT_CLICKED;
-        const LV_EVENT_CLICKED: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_CLICKED;
-        const LV_EVENT_LONG_PRESSED: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_LONG_PRESSED;
-        const LV_EVENT_LONG_PRESSED_REPEAT: u32 =
-            lvgl_sys::lv_event_code_t_LV_EVENT_LONG_PRESSED_REPEAT;
-        const LV_EVENT_RELEASED: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_RELEASED;
-        const LV_EVENT_VALUE_CHANGED: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_VALUE_CHANGED;
-        const LV_EVENT_DRAW_MAIN: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_MAIN;
-        const LV_EVENT_DRAW_MAIN_BEGIN: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_MAIN_BEGIN;
-        const LV_EVENT_DRAW_MAIN_END: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_MAIN_END;
-        const LV_EVENT_DRAW_PART_BEGIN: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_PART_BEGIN;
-        const LV_EVENT_DRAW_PART_END: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_PART_END;
-        const LV_EVENT_DRAW_POST: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_POST;
-        const LV_EVENT_DRAW_POST_BEGIN: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_POST_BEGIN;
-        const LV_EVENT_DRAW_POST_END: u32 = lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_POST_END;
-
-        match value {
-            LV_EVENT_PRESSED => Ok(Event::Pressed),
-            LV_EVENT_PRESSING => Ok(Event::Pressing),
-            LV_EVENT_PRESS_LOST => Ok(Event::PressLost),
-            LV_EVENT_SHORT_CLICKED => Ok(Event::ShortClicked),
-            LV_EVENT_CLICKED => Ok(Event::Clicked),
-            LV_EVENT_LONG_PRESSED => Ok(Event::LongPressed),
-            LV_EVENT_LONG_PRESSED_REPEAT => Ok(Event::LongPressedRepeat),
-            LV_EVENT_RELEASED => Ok(Event::Released),
-            LV_EVENT_VALUE_CHANGED => Ok(Event::ValueChanged),
-            LV_EVENT_DRAW_MAIN => Ok(Event::DrawMain),
-            LV_EVENT_DRAW_MAIN_BEGIN => Ok(Event::DrawMainBegin),
-            LV_EVENT_DRAW_MAIN_END => Ok(Event::DrawMainEnd),
-            LV_EVENT_DRAW_PART_BEGIN => Ok(Event::DrawPartBegin),
-            LV_EVENT_DRAW_PART_END => Ok(Event::DrawPartEnd),
-            LV_EVENT_DRAW_POST => Ok(Event::DrawPost),
-            LV_EVENT_DRAW_POST_BEGIN => Ok(Event::DrawPostBegin),
-            LV_EVENT_DRAW_POST_END => Ok(Event::DrawPostEnd),
-            _ => Err(()),
-        }
+        let event = match value {
+            lvgl_sys::lv_event_code_t_LV_EVENT_PRESSED => Event::Pressed,
+            lvgl_sys::lv_event_code_t_LV_EVENT_PRESSING => Event::Pressing,
+            lvgl_sys::lv_event_code_t_LV_EVENT_PRESS_LOST => Event::PressLost,
+            lvgl_sys::lv_event_code_t_LV_EVENT_SHORT_CLICKED => Event::ShortClicked,
+            lvgl_sys::lv_event_code_t_LV_EVENT_LONG_PRESSED => Event::LongPressed,
+            lvgl_sys::lv_event_code_t_LV_EVENT_LONG_PRESSED_REPEAT => Event::LongPressedRepeat,
+            lvgl_sys::lv_event_code_t_LV_EVENT_CLICKED => Event::Clicked,
+            lvgl_sys::lv_event_code_t_LV_EVENT_RELEASED => Event::Released,
+            lvgl_sys::lv_event_code_t_LV_EVENT_SCROLL_BEGIN => Event::ScrollBegin,
+            lvgl_sys::lv_event_code_t_LV_EVENT_SCROLL_END => Event::ScrollEnd,
+            lvgl_sys::lv_event_code_t_LV_EVENT_SCROLL => Event::Scroll,
+            lvgl_sys::lv_event_code_t_LV_EVENT_FOCUSED => Event::Focused,
+            lvgl_sys::lv_event_code_t_LV_EVENT_DEFOCUSED => Event::Defocused,
+            lvgl_sys::lv_event_code_t_LV_EVENT_LEAVE => Event::Leave,
+            lvgl_sys::lv_event_code_t_LV_EVENT_HIT_TEST => Event::HitTest,
+            lvgl_sys::lv_event_code_t_LV_EVENT_COVER_CHECK => Event::CoverCheck,
+            lvgl_sys::lv_event_code_t_LV_EVENT_REFR_EXT_DRAW_SIZE => Event::RefrExtDrawSize,
+            lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_MAIN_BEGIN => Event::DrawMainBegin,
+            lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_MAIN => Event::DrawMain,
+            lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_MAIN_END => Event::DrawMainEnd,
+            lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_POST_BEGIN => Event::DrawPostBegin,
+            lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_POST => Event::DrawPost,
+            lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_POST_END => Event::DrawPostEnd,
+            lvgl_sys::lv_event_code_t_LV_EVENT_VALUE_CHANGED => Event::ValueChanged,
+            lvgl_sys::lv_event_code_t_LV_EVENT_INSERT => Event::Insert,
+            lvgl_sys::lv_event_code_t_LV_EVENT_REFRESH => Event::Refresh,
+            lvgl_sys::lv_event_code_t_LV_EVENT_READY => Event::Ready,
+            lvgl_sys::lv_event_code_t_LV_EVENT_CANCEL => Event::Cancel,
+            lvgl_sys::lv_event_code_t_LV_EVENT_DELETE => Event::Delete,
+            lvgl_sys::lv_event_code_t_LV_EVENT_CHILD_CHANGED => Event::ChildChanged,
+            lvgl_sys::lv_event_code_t_LV_EVENT_CHILD_CREATED => Event::ChildCreated,
+            lvgl_sys::lv_event_code_t_LV_EVENT_CHILD_DELETED => Event::ChildDeleted,
+            lvgl_sys::lv_event_code_t_LV_EVENT_SCREEN_UNLOAD_START => Event::ScreenUnloadStart,
+            lvgl_sys::lv_event_code_t_LV_EVENT_SCREEN_LOAD_START => Event::ScreenLoadStart,
+            lvgl_sys::lv_event_code_t_LV_EVENT_SCREEN_LOADED => Event::ScreenLoaded,
+            lvgl_sys::lv_event_code_t_LV_EVENT_SCREEN_UNLOADED => Event::ScreenUnloaded,
+            lvgl_sys::lv_event_code_t_LV_EVENT_SIZE_CHANGED => Event::SizeChanged,
+            lvgl_sys::lv_event_code_t_LV_EVENT_STYLE_CHANGED => Event::StyleChanged,
+            lvgl_sys::lv_event_code_t_LV_EVENT_LAYOUT_CHANGED => Event::LayoutChanged,
+            lvgl_sys::lv_event_code_t_LV_EVENT_GET_SELF_SIZE => Event::GetSelfSize,
+            _ => return CustomEventId::from_code(value).map(Event::Custom).ok_or(()),
+        };
+        Ok(event)
     }
 }
 
-impl<S> From<Event<S>> for lvgl_sys::lv_event_code_t {
+impl<S: SpecialEvent> From<Event<S>> for lvgl_sys::lv_event_code_t {
     fn from(event: Event<S>) -> Self {
-        let native_event = match event {
+        match event {
             Event::Pressed => lvgl_sys::lv_event_code_t_LV_EVENT_PRESSED,
             Event::Pressing => lvgl_sys::lv_event_code_t_LV_EVENT_PRESSING,
             Event::PressLost => lvgl_sys::lv_event_code_t_LV_EVENT_PRESS_LOST,
             Event::ShortClicked => lvgl_sys::lv_event_code_t_LV_EVENT_SHORT_CLICKED,
-            Event::Clicked => lvgl_sys::lv_event_code_t_LV_EVENT_CLICKED,
             Event::LongPressed => lvgl_sys::lv_event_code_t_LV_EVENT_LONG_PRESSED,
             Event::LongPressedRepeat => lvgl_sys::lv_event_code_t_LV_EVENT_LONG_PRESSED_REPEAT,
+            Event::Clicked => lvgl_sys::lv_event_code_t_LV_EVENT_CLICKED,
             Event::Released => lvgl_sys::lv_event_code_t_LV_EVENT_RELEASED,
-            Event::ValueChanged => lvgl_sys::lv_event_code_t_LV_EVENT_VALUE_CHANGED,
-            Event::DrawMain => lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_MAIN,
+            Event::ScrollBegin => lvgl_sys::lv_event_code_t_LV_EVENT_SCROLL_BEGIN,
+            Event::ScrollEnd => lvgl_sys::lv_event_code_t_LV_EVENT_SCROLL_END,
+            Event::Scroll => lvgl_sys::lv_event_code_t_LV_EVENT_SCROLL,
+            Event::Gesture(_) => lvgl_sys::lv_event_code_t_LV_EVENT_GESTURE,
+            Event::Key(_) => lvgl_sys::lv_event_code_t_LV_EVENT_KEY,
+            Event::Focused => lvgl_sys::lv_event_code_t_LV_EVENT_FOCUSED,
+            Event::Defocused => lvgl_sys::lv_event_code_t_LV_EVENT_DEFOCUSED,
+            Event::Leave => lvgl_sys::lv_event_code_t_LV_EVENT_LEAVE,
+            Event::HitTest => lvgl_sys::lv_event_code_t_LV_EVENT_HIT_TEST,
+            Event::CoverCheck => lvgl_sys::lv_event_code_t_LV_EVENT_COVER_CHECK,
+            Event::RefrExtDrawSize => lvgl_sys::lv_event_code_t_LV_EVENT_REFR_EXT_DRAW_SIZE,
             Event::DrawMainBegin => lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_MAIN_BEGIN,
+            Event::DrawMain => lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_MAIN,
             Event::DrawMainEnd => lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_MAIN_END,
-            Event::DrawPartBegin => lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_PART_BEGIN,
-            Event::DrawPartEnd => lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_PART_END,
-            Event::DrawPost => lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_POST,
             Event::DrawPostBegin => lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_POST_BEGIN,
+            Event::DrawPost => lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_POST,
             Event::DrawPostEnd => lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_POST_END,
-            // TODO: handle all types...
-            _ => lvgl_sys::lv_event_code_t_LV_EVENT_CLICKED,
-        };
-        native_event as lvgl_sys::lv_event_code_t
+            Event::DrawPartBegin(_) => lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_PART_BEGIN,
+            Event::DrawPartEnd(_) => lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_PART_END,
+            Event::ValueChanged => lvgl_sys::lv_event_code_t_LV_EVENT_VALUE_CHANGED,
+            Event::Insert => lvgl_sys::lv_event_code_t_LV_EVENT_INSERT,
+            Event::Refresh => lvgl_sys::lv_event_code_t_LV_EVENT_REFRESH,
+            Event::Ready => lvgl_sys::lv_event_code_t_LV_EVENT_READY,
+            Event::Cancel => lvgl_sys::lv_event_code_t_LV_EVENT_CANCEL,
+            Event::Delete => lvgl_sys::lv_event_code_t_LV_EVENT_DELETE,
+            Event::ChildChanged => lvgl_sys::lv_event_code_t_LV_EVENT_CHILD_CHANGED,
+            Event::ChildCreated => lvgl_sys::lv_event_code_t_LV_EVENT_CHILD_CREATED,
+            Event::ChildDeleted => lvgl_sys::lv_event_code_t_LV_EVENT_CHILD_DELETED,
+            Event::ScreenUnloadStart => lvgl_sys::lv_event_code_t_LV_EVENT_SCREEN_UNLOAD_START,
+            Event::ScreenLoadStart => lvgl_sys::lv_event_code_t_LV_EVENT_SCREEN_LOAD_START,
+            Event::ScreenLoaded => lvgl_sys::lv_event_code_t_LV_EVENT_SCREEN_LOADED,
+            Event::ScreenUnloaded => lvgl_sys::lv_event_code_t_LV_EVENT_SCREEN_UNLOADED,
+            Event::SizeChanged => lvgl_sys::lv_event_code_t_LV_EVENT_SIZE_CHANGED,
+            Event::StyleChanged => lvgl_sys::lv_event_code_t_LV_EVENT_STYLE_CHANGED,
+            Event::LayoutChanged => lvgl_sys::lv_event_code_t_LV_EVENT_LAYOUT_CHANGED,
+            Event::GetSelfSize => lvgl_sys::lv_event_code_t_LV_EVENT_GET_SELF_SIZE,
+            Event::Custom(id) => id.code(),
+            Event::Special(special) => special.code(),
+        }
+    }
+}
+
+/// Events specific to a type of widget, such as `KeyboardEvent`. LVGL sends
+/// them with generic codes, e.g. `LV_EVENT_READY`, which are told apart by
+/// the type of the widget they are sent to.
+pub trait SpecialEvent: Sized {
+    /// Decodes an event sent to a widget, if it is one of its special events.
+    ///
+    /// # Safety
+    ///
+    /// `event` must be a valid pointer to an event being sent by LVGL to a
+    /// widget with these special events.
+    unsafe fn from_raw(event: *mut lvgl_sys::lv_event_t) -> Option<Self>;
+
+    /// Returns the code the event is sent with.
+    fn code(&self) -> lvgl_sys::lv_event_code_t;
+
+    /// Returns the parameter `event_send` sends the event with, pointed to
+    /// by the parameter of the raw event. `from_raw` must decode it.
+    fn param(&self) -> Option<u32> {
+        None
     }
 }
 
-/// Events sent only by pointer-like input devices (e.g. mouse or touchpad)
+/// The special events of widgets which have none.
+impl SpecialEvent for Infallible {
+    unsafe fn from_raw(_: *mut lvgl_sys::lv_event_t) -> Option<Self> {
+        None
+    }
+
+    fn code(&self) -> lvgl_sys::lv_event_code_t {
+        match *self {}
+    }
+}
+
+/// A part of an object being drawn, sent with `Event::DrawPartBegin` and
+/// `Event::DrawPartEnd`. The draw descriptors it points to are only valid
+/// while handling the event.
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
-pub enum PointerEvent {
-    DragBegin,
-    DragEnd,
-    DragThrowBegin,
+pub struct DrawPart {
+    raw: NonNull<lvgl_sys::lv_obj_draw_part_dsc_t>,
+    part: lvgl_sys::lv_part_t,
+    id: u32,
+    type_: u32,
+    value: i32,
 }
 
-pub(crate) unsafe extern "C" fn event_callback<'a, T, F>(event: *mut lvgl_sys::lv_event_t)
-where
-    T: Widget<'a> + Sized,
-    F: FnMut(T, Event<<T as Widget<'a>>::SpecialEvent>),
-{
-    let code = (*event).code;
-    let obj = (*event).target;
-    // convert the lv_event_code_t to lvgl-rs Event type
-    if let Ok(code) = code.try_into() {
-        if let Some(obj_ptr) = NonNull::new(obj) {
-            let object = T::from_raw(obj_ptr).unwrap();
-            // get the pointer from the Rust callback closure FnMut provided by users
-            let user_closure = &mut *((*obj).user_data as *mut F);
-            // call user callback closure
-            user_closure(object, code);
-        }
+impl DrawPart {
+    unsafe fn from_raw(raw: *mut lvgl_sys::lv_obj_draw_part_dsc_t) -> Option<Self> {
+        let raw = NonNull::new(raw)?;
+        let dsc = raw.as_ref();
+        Some(Self {
+            raw,
+            part: dsc.part,
+            id: dsc.id,
+            type_: dsc.type_,
+            value: dsc.value,
+        })
+    }
+
+    /// The part being drawn, e.g. `LV_PART_INDICATOR`.
+    pub fn part(&self) -> lvgl_sys::lv_part_t {
+        self.part
+    }
+
+    /// The index of the item being drawn, e.g. of a cell of a table.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// The widget-specific kind of the part being drawn.
+    pub fn type_(&self) -> u32 {
+        self.type_
+    }
+
+    /// A widget-specific value of the part being drawn, e.g. of a tick.
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    /// Returns the draw descriptor, to modify how the part is drawn. It is
+    /// only valid while handling the event the part was sent with.
+    pub fn raw(&self) -> *mut lvgl_sys::lv_obj_draw_part_dsc_t {
+        self.raw.as_ptr()
     }
 }
 
@@ -399,10 +673,86 @@ impl From<LabelLongMode> for u8 {
     }
 }
 
+bitflags! {
+    /// Directions, e.g. of a gesture or of scrolling.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Direction: u8 {
+        const LEFT = lvgl_sys::LV_DIR_LEFT as u8;
+        const RIGHT = lvgl_sys::LV_DIR_RIGHT as u8;
+        const TOP = lvgl_sys::LV_DIR_TOP as u8;
+        const BOTTOM = lvgl_sys::LV_DIR_BOTTOM as u8;
+        const HOR = lvgl_sys::LV_DIR_HOR as u8;
+        const VER = lvgl_sys::LV_DIR_VER as u8;
+        const ALL = lvgl_sys::LV_DIR_ALL as u8;
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn color_to_rgb_scales_channels() {
+        assert_eq!(Color::from_rgb((255, 255, 255)).to_rgb(), (255, 255, 255));
+        assert_eq!(Color::from_rgb((255, 0, 0)).to_rgb(), (255, 0, 0));
+        assert_eq!(Color::from_rgb((0, 0, 0)).to_rgb(), (0, 0, 0));
+    }
+
+    #[test]
+    fn event_codes_round_trip() {
+        for code in 1..=lvgl_sys::lv_event_code_t_LV_EVENT_GET_SELF_SIZE {
+            match Event::<Infallible>::try_from(code) {
+                Ok(event) => assert_eq!(lvgl_sys::lv_event_code_t::from(event), code),
+                Err(()) => assert!([
+                    lvgl_sys::lv_event_code_t_LV_EVENT_GESTURE,
+                    lvgl_sys::lv_event_code_t_LV_EVENT_KEY,
+                    lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_PART_BEGIN,
+                    lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_PART_END,
+                ]
+                .contains(&code)),
+            }
+        }
+        assert_eq!(
+            lvgl_sys::lv_event_code_t::from(Event::<Infallible>::Key(KeypadInputData::Enter)),
+            lvgl_sys::lv_event_code_t_LV_EVENT_KEY
+        );
+
+        let custom = CustomEventId::register();
+        assert_ne!(CustomEventId::register(), custom);
+        assert_eq!(
+            Event::<Infallible>::try_from(custom.code()),
+            Ok(Event::Custom(custom))
+        );
+        assert_eq!(
+            lvgl_sys::lv_event_code_t::from(Event::<Infallible>::Custom(custom)),
+            custom.code()
+        );
+        assert_eq!(
+            CustomEventId::from_code(lvgl_sys::lv_event_code_t_LV_EVENT_CLICKED),
+            None
+        );
+    }
+
+    #[test]
+    fn send_key_event() {
+        crate::tests::initialize_test(true);
+        let mut btn = crate::widgets::Btn::new().unwrap();
+        let keys = std::rc::Rc::new(core::cell::Cell::new(0));
+        let pressed = keys.clone();
+        btn.on_event(move |_, event, _| {
+            if event == Event::Key(KeypadInputData::Char('a')) {
+                pressed.set(pressed.get() + 1);
+            }
+        })
+        .unwrap();
+
+        crate::event_send(&mut btn, Event::Key(KeypadInputData::Char('a'))).unwrap();
+        assert_eq!(keys.get(), 1);
+        // Without an active input device, LVGL would drop the gesture
+        assert!(crate::event_send(&mut btn, Event::Gesture(Direction::LEFT)).is_err());
+        unsafe { lvgl_sys::lv_obj_del(crate::NativeObject::raw(&btn).as_ptr()) };
+    }
+
     #[test]
     fn color_properties_accessible() {
         let color = Color::from_rgb((206, 51, 255));
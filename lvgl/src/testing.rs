@@ -0,0 +1,305 @@
+//! Golden-image snapshot testing.
+//!
+//! Renders the active screen of a `FramebufferDisplay` and compares it to a
+//! reference image stored on disk, so that changes to layouts and styles
+//! show up as test failures:
+//!
+//! ```no_run
+//! use lvgl::framebuffer::FramebufferDisplay;
+//! use lvgl::testing::assert_snapshot;
+//! use lvgl::DrawBuffer;
+//!
+//! let buffer = DrawBuffer::<{ 320 * 24 }>::default();
+//! let display = FramebufferDisplay::register(buffer, 320, 240).unwrap();
+//! // ... create some widgets ...
+//! assert_snapshot(&display, "main_menu");
+//! ```
+//!
+//! Reference images are stored as binary PPM files. Missing references fail
+//! the comparison, so that a test never passes without checking anything;
+//! run the tests with the `LVGL_UPDATE_SNAPSHOTS` environment variable set to
+//! write the rendered frames as the new references instead. On a mismatch,
+//! the rendered frame and an image highlighting the differing pixels in red
+//! are written next to the reference.
+
+extern crate std;
+
+use crate::framebuffer::{encode_ppm, FramebufferDisplay};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable which, when set, writes all reference images instead
+/// of comparing against them.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "LVGL_UPDATE_SNAPSHOTS";
+
+/// Where reference images are stored and how strictly they are compared.
+#[derive(Debug, Clone)]
+pub struct SnapshotConfig {
+    /// Directory of the reference images. Defaults to `tests/snapshots` in
+    /// the directory of the crate under test.
+    pub dir: PathBuf,
+    /// Maximum difference of each color channel for pixels to still be
+    /// considered equal.
+    pub tolerance: u8,
+    /// Number of pixels which may differ before the comparison fails.
+    pub max_mismatched_pixels: usize,
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        let root = env::var_os("CARGO_MANIFEST_DIR").map_or_else(PathBuf::new, PathBuf::from);
+        Self {
+            dir: root.join("tests").join("snapshots"),
+            tolerance: 0,
+            max_mismatched_pixels: 0,
+        }
+    }
+}
+
+/// Error in comparing a snapshot.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The reference image could not be read or the results written.
+    Io(io::Error),
+    /// There is no reference image, and `UPDATE_SNAPSHOTS_VAR` is not set.
+    MissingReference(PathBuf),
+    /// The reference image is not a valid binary PPM file.
+    InvalidReference(PathBuf),
+    /// The reference image has a different resolution than the display.
+    SizeMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    /// Too many pixels differ from the reference image.
+    Mismatch {
+        mismatched_pixels: usize,
+        diff: PathBuf,
+    },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(err) => write!(f, "Snapshot I/O error: {}", err),
+            SnapshotError::MissingReference(path) => write!(
+                f,
+                "Missing reference image {}, set {} to write it",
+                path.display(),
+                UPDATE_SNAPSHOTS_VAR
+            ),
+            SnapshotError::InvalidReference(path) => {
+                write!(f, "Invalid reference image {}", path.display())
+            }
+            SnapshotError::SizeMismatch { expected, actual } => write!(
+                f,
+                "Snapshot is {}x{}, but the reference image is {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+            SnapshotError::Mismatch {
+                mismatched_pixels,
+                diff,
+            } => write!(
+                f,
+                "{} pixels differ from the reference image, see {}",
+                mismatched_pixels,
+                diff.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<io::Error> for SnapshotError {
+    fn from(err: io::Error) -> Self {
+        SnapshotError::Io(err)
+    }
+}
+
+/// Renders the display and compares it to the reference image `name`,
+/// panicking if they differ. See `check_snapshot`.
+#[track_caller]
+pub fn assert_snapshot(display: &FramebufferDisplay, name: &str) {
+    if let Err(err) = check_snapshot(display, name, &SnapshotConfig::default()) {
+        panic!("Snapshot `{}` does not match: {}", name, err);
+    }
+}
+
+/// Renders the display with `lv_refr_now` and compares the frame to the
+/// reference image `name` in `config.dir`. If `UPDATE_SNAPSHOTS_VAR` is set,
+/// the frame is written as the reference instead.
+pub fn check_snapshot(
+    display: &FramebufferDisplay,
+    name: &str,
+    config: &SnapshotConfig,
+) -> Result<(), SnapshotError> {
+    display.refresh();
+    let actual = display.to_rgb();
+    let size = (display.width(), display.height());
+    let reference = config.dir.join(format!("{}.ppm", name));
+
+    if env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() {
+        fs::create_dir_all(&config.dir)?;
+        fs::write(&reference, display.to_ppm())?;
+        return Ok(());
+    }
+    if !reference.exists() {
+        return Err(SnapshotError::MissingReference(reference));
+    }
+
+    let (expected_size, expected) = read_ppm(&reference)?;
+    if expected_size != size {
+        return Err(SnapshotError::SizeMismatch {
+            expected: expected_size,
+            actual: size,
+        });
+    }
+
+    let mut diff = Vec::with_capacity(actual.len());
+    let mut mismatched_pixels = 0;
+    for (actual, expected) in actual.chunks(3).zip(expected.chunks(3)) {
+        let matches = actual
+            .iter()
+            .zip(expected)
+            .all(|(a, e)| a.abs_diff(*e) <= config.tolerance);
+        if matches {
+            // Dimmed grayscale version of the pixel, to give some context
+            let luma = (actual[0] as u16 + actual[1] as u16 + actual[2] as u16) / 3;
+            let dimmed = (luma / 4 + 96) as u8;
+            diff.extend([dimmed, dimmed, dimmed]);
+        } else {
+            mismatched_pixels += 1;
+            diff.extend([255, 0, 0]);
+        }
+    }
+
+    if mismatched_pixels > config.max_mismatched_pixels {
+        let diff_path = config.dir.join(format!("{}.diff.ppm", name));
+        fs::write(
+            config.dir.join(format!("{}.actual.ppm", name)),
+            display.to_ppm(),
+        )?;
+        fs::write(&diff_path, encode_ppm(size.0, size.1, &diff))?;
+        return Err(SnapshotError::Mismatch {
+            mismatched_pixels,
+            diff: diff_path,
+        });
+    }
+    Ok(())
+}
+
+/// Reads a binary PPM file with 8 bits per channel, returning its size and
+/// pixels.
+fn read_ppm(path: &Path) -> Result<((u32, u32), Vec<u8>), SnapshotError> {
+    let invalid = || SnapshotError::InvalidReference(path.to_path_buf());
+    let data = fs::read(path)?;
+
+    // The header consists of four whitespace-separated fields, followed by a
+    // single whitespace character.
+    let mut fields: Vec<String> = Vec::with_capacity(4);
+    let mut field = String::new();
+    let mut pixels_start = None;
+    for (i, &byte) in data.iter().enumerate() {
+        if byte.is_ascii_whitespace() {
+            if !field.is_empty() {
+                fields.push(core::mem::take(&mut field));
+            }
+            if fields.len() == 4 {
+                pixels_start = Some(i + 1);
+                break;
+            }
+        } else {
+            field.push(byte as char);
+        }
+    }
+
+    let pixels_start = pixels_start.ok_or_else(invalid)?;
+    let parse = |field: &str| field.parse::<u32>().map_err(|_| invalid());
+    if fields[0] != "P6" || parse(&fields[3])? != 255 {
+        return Err(invalid());
+    }
+    let size = (parse(&fields[1])?, parse(&fields[2])?);
+    let len = (size.0 as usize)
+        .checked_mul(size.1 as usize)
+        .and_then(|pixels| pixels.checked_mul(3))
+        .ok_or_else(invalid)?;
+    let pixels = &data[pixels_start..];
+    if pixels.len() != len {
+        return Err(invalid());
+    }
+    Ok((size, pixels.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::set_screen_color;
+    use crate::DrawBuffer;
+
+    #[test]
+    fn compare_snapshots() {
+        crate::tests::initialize_test(false);
+        let buffer = DrawBuffer::<{ 32 * 8 }>::default();
+        let display = FramebufferDisplay::register(buffer, 32, 16).unwrap();
+        let config = SnapshotConfig {
+            dir: env::temp_dir().join("lvgl-snapshot-test"),
+            ..Default::default()
+        };
+        let _ = fs::remove_dir_all(&config.dir);
+
+        set_screen_color(&display, (255, 0, 0));
+        assert!(matches!(
+            check_snapshot(&display, "screen", &config),
+            Err(SnapshotError::MissingReference(_))
+        ));
+        assert!(!config.dir.exists());
+        fs::create_dir_all(&config.dir).unwrap();
+        fs::write(config.dir.join("screen.ppm"), display.to_ppm()).unwrap();
+        check_snapshot(&display, "screen", &config).unwrap();
+
+        set_screen_color(&display, (0, 0, 255));
+        match check_snapshot(&display, "screen", &config) {
+            Err(SnapshotError::Mismatch {
+                mismatched_pixels,
+                diff,
+            }) => {
+                assert_eq!(mismatched_pixels, 32 * 16);
+                let (size, _) = read_ppm(&diff).unwrap();
+                assert_eq!(size, (32, 16));
+            }
+            other => panic!("Expected a mismatch, got {:?}", other),
+        }
+
+        let tolerant = SnapshotConfig {
+            tolerance: 255,
+            ..config
+        };
+        check_snapshot(&display, "screen", &tolerant).unwrap();
+    }
+
+    #[test]
+    fn reject_invalid_references() {
+        let dir = env::temp_dir().join("lvgl-snapshot-invalid");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("invalid.ppm");
+        for ppm in [
+            &b"P6\n2 1\n255\n\0\0\0"[..],
+            b"P6\n4294967295 4294967295\n255\n\0\0\0",
+            b"P3\n1 1\n255\n\0\0\0",
+        ] {
+            fs::write(&path, ppm).unwrap();
+            assert!(matches!(
+                read_ppm(&path),
+                Err(SnapshotError::InvalidReference(_))
+            ));
+        }
+        fs::write(&path, b"P6\n1 1\n255\n\0\0\0").unwrap();
+        assert_eq!(read_ppm(&path).unwrap(), ((1, 1), vec![0, 0, 0]));
+    }
+}
@@ -0,0 +1,211 @@
+use crate::widgets::Canvas;
+use crate::{Box, Color, Event, LvError, LvResult, NativeObject, Widget};
+use core::pin::Pin;
+
+/// Pixel memory of a `Canvas`, holding up to `N` pixels in the native color
+/// format.
+pub struct CanvasBuffer<const N: usize> {
+    pixels: Pin<Box<[lvgl_sys::lv_color_t; N]>>,
+}
+
+impl<const N: usize> Default for CanvasBuffer<N> {
+    fn default() -> Self {
+        Self {
+            pixels: Box::pin([Color::default().raw; N]),
+        }
+    }
+}
+
+impl<'a> Canvas<'a> {
+    /// Sets the buffer the canvas draws into and displays, using `width` by
+    /// `height` of its pixels. The buffer must be large enough to hold them.
+    /// The canvas takes ownership of the buffer and frees it when it is
+    /// deleted; buffers replaced by another call are also kept until then.
+    pub fn set_buffer<const N: usize>(
+        &mut self,
+        mut buffer: CanvasBuffer<N>,
+        width: u32,
+        height: u32,
+    ) -> LvResult<()> {
+        if (width as usize) * (height as usize) > N {
+            return Err(LvError::InvalidReference);
+        }
+        let pixels = buffer.pixels.as_mut_ptr();
+        // The closure and the buffer are freed after the `Delete` event
+        self.add_event_handler(Some(Event::Delete), move |_, _, _| {
+            let _buffer = &buffer;
+        })?;
+        unsafe {
+            lvgl_sys::lv_canvas_set_buffer(
+                self.core.raw().as_mut(),
+                pixels as *mut _,
+                width as lvgl_sys::lv_coord_t,
+                height as lvgl_sys::lv_coord_t,
+                lvgl_sys::LV_IMG_CF_TRUE_COLOR as lvgl_sys::lv_img_cf_t,
+            );
+        }
+        Ok(())
+    }
+
+    /// Fills the whole canvas with a color.
+    pub fn fill_bg(&mut self, color: Color) {
+        unsafe {
+            lvgl_sys::lv_canvas_fill_bg(
+                self.core.raw().as_mut(),
+                color.raw,
+                lvgl_sys::LV_OPA_COVER as lvgl_sys::lv_opa_t,
+            );
+        }
+    }
+
+    /// Returns the color of the pixel at the given coordinates.
+    pub fn get_px(&self, x: i16, y: i16) -> Color {
+        Color::from_raw(unsafe {
+            lvgl_sys::lv_canvas_get_px(
+                self.core.raw().as_ptr(),
+                x as lvgl_sys::lv_coord_t,
+                y as lvgl_sys::lv_coord_t,
+            )
+        })
+    }
+}
+
+#[cfg(feature = "embedded_graphics")]
+mod embedded_graphics_imp {
+    use crate::widgets::Canvas;
+    use crate::{Color, LvError, LvResult, NativeObject};
+    use core::slice;
+    use embedded_graphics::draw_target::DrawTarget;
+    use embedded_graphics::geometry::{OriginDimensions, Size};
+    use embedded_graphics::pixelcolor::Rgb888;
+    use embedded_graphics::Pixel;
+
+    impl Canvas<'_> {
+        /// Returns the buffer and size set with `set_buffer`. Fails if there
+        /// is no buffer, or if it was set by LVGL in a color format other
+        /// than `LV_IMG_CF_TRUE_COLOR`.
+        fn pixels_mut(&mut self) -> LvResult<(&mut [lvgl_sys::lv_color_t], Size)> {
+            let dsc = unsafe { lvgl_sys::lv_canvas_get_img(self.core.raw().as_ptr()).as_ref() }
+                .ok_or(LvError::InvalidReference)?;
+            let size = Size::new(dsc.header.w(), dsc.header.h());
+            if dsc.data.is_null() || dsc.header.cf() as u32 != lvgl_sys::LV_IMG_CF_TRUE_COLOR {
+                return Err(LvError::InvalidReference);
+            }
+            // The data is the CanvasBuffer owned by the canvas
+            let pixels = unsafe {
+                slice::from_raw_parts_mut(
+                    dsc.data as *mut lvgl_sys::lv_color_t,
+                    (size.width * size.height) as usize,
+                )
+            };
+            Ok((pixels, size))
+        }
+    }
+
+    /// Draws into the buffer of the canvas. The canvas is invalidated once per
+    /// draw call, so it is redrawn on the next refresh. Drawing fails unless a
+    /// `CanvasBuffer` was set with `Canvas::set_buffer`, as other buffers
+    /// may not hold native colors.
+    impl DrawTarget for Canvas<'_> {
+        type Color = Rgb888;
+        type Error = LvError;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            let (buffer, size) = self.pixels_mut()?;
+            let mut drawn = false;
+            for Pixel(point, color) in pixels {
+                let (x, y) = (point.x as u32, point.y as u32);
+                if point.x >= 0 && point.y >= 0 && x < size.width && y < size.height {
+                    buffer[(y * size.width + x) as usize] = Color::from(color).raw;
+                    drawn = true;
+                }
+            }
+            if drawn {
+                unsafe { lvgl_sys::lv_obj_invalidate(self.core.raw().as_ptr()) };
+            }
+            Ok(())
+        }
+
+        fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+            self.fill_bg(color.into());
+            Ok(())
+        }
+    }
+
+    impl OriginDimensions for Canvas<'_> {
+        fn size(&self) -> Size {
+            let dsc = unsafe { lvgl_sys::lv_canvas_get_img(self.core.raw().as_ptr()).as_ref() };
+            dsc.map_or(Size::zero(), |dsc| {
+                Size::new(dsc.header.w(), dsc.header.h())
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Widget;
+
+    #[test]
+    fn draw_into_canvas() {
+        crate::tests::initialize_test(true);
+        let small_buffer = CanvasBuffer::<{ 16 * 4 }>::default();
+        let buffer = CanvasBuffer::<{ 16 * 8 }>::default();
+        let mut canvas = Canvas::new().unwrap();
+        assert!(canvas.set_buffer(small_buffer, 16, 8).is_err());
+        canvas.set_buffer(buffer, 16, 8).unwrap();
+        canvas.set_size(16, 8);
+
+        canvas.fill_bg(Color::from_rgb((255, 0, 0)));
+        assert_eq!(canvas.get_px(15, 7).to_rgb(), (255, 0, 0));
+        unsafe { lvgl_sys::lv_obj_del(canvas.raw().as_ptr()) };
+    }
+
+    #[cfg(feature = "embedded_graphics")]
+    #[test]
+    fn draw_embedded_graphics() {
+        use embedded_graphics::pixelcolor::Rgb888;
+        use embedded_graphics::prelude::*;
+        use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+
+        crate::tests::initialize_test(true);
+        let mut canvas = Canvas::new().unwrap();
+        canvas
+            .set_buffer(CanvasBuffer::<{ 16 * 8 }>::default(), 16, 8)
+            .unwrap();
+        assert_eq!(canvas.size(), Size::new(16, 8));
+
+        canvas.clear(Rgb888::BLACK).unwrap();
+        Rectangle::new(Point::new(4, 2), Size::new(4, 4))
+            .into_styled(PrimitiveStyle::with_fill(Rgb888::WHITE))
+            .draw(&mut canvas)
+            .unwrap();
+        Pixel(Point::new(20, 20), Rgb888::WHITE)
+            .draw(&mut canvas)
+            .unwrap();
+
+        assert_eq!(canvas.get_px(5, 3).to_rgb(), (255, 255, 255));
+        assert_eq!(canvas.get_px(0, 0).to_rgb(), (0, 0, 0));
+
+        // Buffers in other color formats are not drawn into
+        let mut alpha = [0u8; 16 * 8];
+        unsafe {
+            lvgl_sys::lv_canvas_set_buffer(
+                canvas.raw().as_ptr(),
+                alpha.as_mut_ptr() as *mut _,
+                16,
+                8,
+                lvgl_sys::LV_IMG_CF_ALPHA_8BIT as lvgl_sys::lv_img_cf_t,
+            );
+        }
+        assert!(Pixel(Point::new(0, 0), Rgb888::WHITE)
+            .draw(&mut canvas)
+            .is_err());
+        assert_eq!(alpha, [0u8; 16 * 8]);
+        unsafe { lvgl_sys::lv_obj_del(canvas.raw().as_ptr()) };
+    }
+}
@@ -0,0 +1,74 @@
+use crate::SpecialEvent;
+
+/// Special events of a `Chart`.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub enum ChartEvent {
+    /// The point with the given index was pressed.
+    PointPressed(u32),
+}
+
+impl SpecialEvent for ChartEvent {
+    unsafe fn from_raw(event: *mut lvgl_sys::lv_event_t) -> Option<Self> {
+        if lvgl_sys::lv_event_get_code(event) != lvgl_sys::lv_event_code_t_LV_EVENT_VALUE_CHANGED {
+            return None;
+        }
+        // LVGL sends the event without a parameter; `event_send` sends the
+        // index of the point
+        let param = lvgl_sys::lv_event_get_param(event) as *const u32;
+        let id = match param.as_ref() {
+            Some(id) => *id,
+            None => {
+                lvgl_sys::lv_chart_get_pressed_point(lvgl_sys::lv_event_get_current_target(event))
+            }
+        };
+        match id {
+            lvgl_sys::LV_CHART_POINT_NONE => None,
+            id => Some(ChartEvent::PointPressed(id)),
+        }
+    }
+
+    fn code(&self) -> lvgl_sys::lv_event_code_t {
+        match self {
+            ChartEvent::PointPressed(_) => lvgl_sys::lv_event_code_t_LV_EVENT_VALUE_CHANGED,
+        }
+    }
+
+    fn param(&self) -> Option<u32> {
+        match self {
+            ChartEvent::PointPressed(id) => Some(*id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::widgets::Chart;
+    use crate::{event_send, Event, NativeObject, Widget};
+    use core::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn chart_special_events() {
+        crate::tests::initialize_test(true);
+        let mut chart = Chart::new().unwrap();
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let received = events.clone();
+        chart
+            .add_event_handler(None, move |_, event, _| received.borrow_mut().push(event))
+            .unwrap();
+
+        // The index of the point is sent as the parameter of the event
+        event_send(&mut chart, Event::Special(ChartEvent::PointPressed(3))).unwrap();
+        // Without a pressed point, the generic event is delivered
+        event_send(&mut chart, Event::ValueChanged).unwrap();
+        assert_eq!(
+            *events.borrow(),
+            [
+                Event::Special(ChartEvent::PointPressed(3)),
+                Event::ValueChanged
+            ]
+        );
+        unsafe { lvgl_sys::lv_obj_del(chart.raw().as_ptr()) };
+    }
+}
@@ -0,0 +1,61 @@
+use crate::SpecialEvent;
+
+/// Special events of a `Dropdown`.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub enum DropdownEvent {
+    /// The list of options was opened.
+    Opened,
+    /// The list of options was closed.
+    Closed,
+}
+
+impl SpecialEvent for DropdownEvent {
+    unsafe fn from_raw(event: *mut lvgl_sys::lv_event_t) -> Option<Self> {
+        match lvgl_sys::lv_event_get_code(event) {
+            lvgl_sys::lv_event_code_t_LV_EVENT_READY => Some(DropdownEvent::Opened),
+            lvgl_sys::lv_event_code_t_LV_EVENT_CANCEL => Some(DropdownEvent::Closed),
+            _ => None,
+        }
+    }
+
+    fn code(&self) -> lvgl_sys::lv_event_code_t {
+        match self {
+            DropdownEvent::Opened => lvgl_sys::lv_event_code_t_LV_EVENT_READY,
+            DropdownEvent::Closed => lvgl_sys::lv_event_code_t_LV_EVENT_CANCEL,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::widgets::Dropdown;
+    use crate::{Event, NativeObject, Widget};
+    use core::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn dropdown_special_events() {
+        crate::tests::initialize_test(true);
+        let mut dropdown = Dropdown::new().unwrap();
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let received = events.clone();
+        dropdown
+            .add_event_handler(None, move |_, event, _| {
+                if let Event::Special(event) = event {
+                    received.borrow_mut().push(event);
+                }
+            })
+            .unwrap();
+
+        unsafe {
+            lvgl_sys::lv_dropdown_open(dropdown.raw().as_ptr());
+            lvgl_sys::lv_dropdown_close(dropdown.raw().as_ptr());
+        }
+        assert_eq!(
+            *events.borrow(),
+            [DropdownEvent::Opened, DropdownEvent::Closed]
+        );
+        unsafe { lvgl_sys::lv_obj_del(dropdown.raw().as_ptr()) };
+    }
+}
@@ -0,0 +1,52 @@
+use crate::widgets::Img;
+#[cfg(feature = "snapshot")]
+use crate::{misc::image::ImageBuf, Event, LvResult, NativeObject, Widget};
+
+impl<'a> Img<'a> {
+    /// Displays an image owned by Rust. The widget takes ownership of the
+    /// image and frees it when it is deleted; images replaced by another call
+    /// are also kept until then.
+    #[cfg(feature = "snapshot")]
+    pub fn set_src(&mut self, image: ImageBuf) -> LvResult<()> {
+        let src = image.raw() as *const lvgl_sys::lv_img_dsc_t;
+        // The closure and the image are freed after the `Delete` event
+        self.add_event_handler(Some(Event::Delete), move |_, _, _| {
+            let _image = &image;
+        })?;
+        unsafe {
+            lvgl_sys::lv_img_set_src(self.core.raw().as_mut(), src as *const _);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "snapshot"))]
+mod tests {
+    use crate::misc::image::ColorFormat;
+    use crate::widgets::{Btn, Img};
+    use crate::{NativeObject, Widget};
+
+    #[test]
+    fn snapshot_into_img() {
+        crate::tests::initialize_test(true);
+        let mut btn = Btn::new().unwrap();
+        btn.set_size(40, 20);
+
+        let image = btn.snapshot(ColorFormat::TrueColor).unwrap();
+        assert!(image.width() >= 40);
+        assert!(image.height() >= 20);
+        assert_eq!(image.color_format(), Some(ColorFormat::TrueColor));
+        assert_eq!(image.data().len(), image.raw().data_size as usize);
+
+        let src = image.raw() as *const lvgl_sys::lv_img_dsc_t;
+        let mut img = Img::new().unwrap();
+        img.set_src(image).unwrap();
+        assert_eq!(
+            unsafe { lvgl_sys::lv_img_get_src(img.raw().as_ptr()) },
+            src as *const _
+        );
+        // Frees the image
+        unsafe { lvgl_sys::lv_obj_del(img.raw().as_ptr()) };
+        unsafe { lvgl_sys::lv_obj_del(btn.raw().as_ptr()) };
+    }
+}
@@ -1,5 +1,31 @@
 use crate::widgets::{Keyboard, Textarea};
-use crate::NativeObject;
+use crate::{NativeObject, SpecialEvent};
+
+/// Special events of a `Keyboard`.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub enum KeyboardEvent {
+    /// The OK key was pressed.
+    Ready,
+    /// The close key was pressed.
+    Cancel,
+}
+
+impl SpecialEvent for KeyboardEvent {
+    unsafe fn from_raw(event: *mut lvgl_sys::lv_event_t) -> Option<Self> {
+        match lvgl_sys::lv_event_get_code(event) {
+            lvgl_sys::lv_event_code_t_LV_EVENT_READY => Some(KeyboardEvent::Ready),
+            lvgl_sys::lv_event_code_t_LV_EVENT_CANCEL => Some(KeyboardEvent::Cancel),
+            _ => None,
+        }
+    }
+
+    fn code(&self) -> lvgl_sys::lv_event_code_t {
+        match self {
+            KeyboardEvent::Ready => lvgl_sys::lv_event_code_t_LV_EVENT_READY,
+            KeyboardEvent::Cancel => lvgl_sys::lv_event_code_t_LV_EVENT_CANCEL,
+        }
+    }
+}
 
 impl Keyboard<'_> {
     /// Associates a given `Textarea` to the keyboard.
@@ -12,3 +38,41 @@ impl Keyboard<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{event_send, Event, NativeObject, Widget};
+    use core::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn keyboard_special_events() {
+        crate::tests::initialize_test(true);
+        let mut keyboard = Keyboard::new().unwrap();
+        let ready = Rc::new(Cell::new(0));
+        let clicks = Rc::new(Cell::new(0));
+        let (readied, clicked) = (ready.clone(), clicks.clone());
+        keyboard
+            .add_event_handler(
+                Some(Event::Special(KeyboardEvent::Ready)),
+                move |_, event, _| {
+                    assert_eq!(event, Event::Special(KeyboardEvent::Ready));
+                    readied.set(readied.get() + 1);
+                },
+            )
+            .unwrap();
+        keyboard
+            .add_event_handler(Some(Event::Clicked), move |_, _, _| {
+                clicked.set(clicked.get() + 1)
+            })
+            .unwrap();
+
+        event_send(&mut keyboard, Event::Special(KeyboardEvent::Ready)).unwrap();
+        event_send(&mut keyboard, Event::Special(KeyboardEvent::Cancel)).unwrap();
+        event_send(&mut keyboard, Event::Clicked).unwrap();
+        assert_eq!(ready.get(), 1);
+        assert_eq!(clicks.get(), 1);
+        unsafe { lvgl_sys::lv_obj_del(keyboard.raw().as_ptr()) };
+    }
+}
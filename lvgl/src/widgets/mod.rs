@@ -7,19 +7,30 @@
 
 mod arc;
 mod bar;
+mod canvas;
+mod chart;
+mod dropdown;
+mod img;
 mod keyboard;
 mod label;
 mod meter;
+mod msgbox;
 mod slider;
 mod table;
+mod textarea;
 
 include!(concat!(env!("OUT_DIR"), "/generated.rs"));
 
 use crate::NativeObject;
 pub use arc::*;
 pub use bar::*;
+pub use canvas::*;
+pub use chart::*;
+pub use dropdown::*;
 pub use keyboard::*;
 pub use label::*;
 pub use meter::*;
+pub use msgbox::*;
 pub use slider::*;
 pub use table::*;
+pub use textarea::*;
@@ -0,0 +1,210 @@
+use crate::{LvError, LvResult, NativeObject, Obj, SpecialEvent, Widget};
+use core::ffi::c_void;
+use core::mem;
+use core::ptr::{self, NonNull};
+use cstr_core::CStr;
+use cty::c_char;
+
+/// Special events of a `Msgbox`.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub enum MsgboxEvent {
+    /// The button with the given index was clicked.
+    ButtonClicked(u16),
+}
+
+impl SpecialEvent for MsgboxEvent {
+    unsafe fn from_raw(event: *mut lvgl_sys::lv_event_t) -> Option<Self> {
+        if lvgl_sys::lv_event_get_code(event) != lvgl_sys::lv_event_code_t_LV_EVENT_VALUE_CHANGED {
+            return None;
+        }
+        // Sent by the buttons of the message box, and bubbled up to it, with
+        // the index of the button as the parameter
+        let param = lvgl_sys::lv_event_get_param(event) as *const u32;
+        let id = match param.as_ref() {
+            Some(id) => *id,
+            None => {
+                lvgl_sys::lv_msgbox_get_active_btn(lvgl_sys::lv_event_get_current_target(event))
+                    as u32
+            }
+        };
+        match id {
+            lvgl_sys::LV_BTNMATRIX_BTN_NONE => None,
+            id => Some(MsgboxEvent::ButtonClicked(id as u16)),
+        }
+    }
+
+    fn code(&self) -> lvgl_sys::lv_event_code_t {
+        match self {
+            MsgboxEvent::ButtonClicked(_) => lvgl_sys::lv_event_code_t_LV_EVENT_VALUE_CHANGED,
+        }
+    }
+
+    fn param(&self) -> Option<u32> {
+        match self {
+            MsgboxEvent::ButtonClicked(id) => Some(*id as u32),
+        }
+    }
+}
+
+define_object!(Msgbox, event = MsgboxEvent);
+
+impl crate::WidgetClass for Msgbox<'_> {
+    fn class() -> &'static lvgl_sys::lv_obj_class_t {
+        unsafe { &lvgl_sys::lv_msgbox_class }
+    }
+}
+
+impl<'a> Msgbox<'a> {
+    /// Creates a message box with a title, a text and a row of `buttons`,
+    /// optionally with a close button in its header. LVGL keeps referring to
+    /// the button texts, so they must outlive the message box.
+    pub fn create(
+        parent: &mut impl NativeObject,
+        title: &CStr,
+        text: &CStr,
+        buttons: &[&'a CStr],
+        add_close_btn: bool,
+    ) -> LvResult<Self> {
+        unsafe {
+            // LVGL expects the map of the buttons to be terminated by an
+            // empty string, and keeps it until the message box is deleted
+            let map = if buttons.is_empty() {
+                ptr::null_mut()
+            } else {
+                let size = (buttons.len() + 1) * mem::size_of::<*const c_char>();
+                let map = lvgl_sys::lv_mem_alloc(size as cty::size_t) as *mut *const c_char;
+                if map.is_null() {
+                    return Err(LvError::LvOOMemory);
+                }
+                for (i, button) in buttons.iter().enumerate() {
+                    map.add(i).write(button.as_ptr());
+                }
+                map.add(buttons.len()).write(&END);
+                map
+            };
+            let ptr = lvgl_sys::lv_msgbox_create(
+                parent.raw().as_mut(),
+                title.as_ptr(),
+                text.as_ptr(),
+                map,
+                add_close_btn,
+            );
+            match NonNull::new(ptr) {
+                Some(raw) => {
+                    if !map.is_null() {
+                        lvgl_sys::lv_obj_add_event_cb(
+                            ptr,
+                            Some(free_map),
+                            lvgl_sys::lv_event_code_t_LV_EVENT_DELETE,
+                            map as *mut c_void,
+                        );
+                    }
+                    let core = <Obj as Widget>::from_raw(raw).unwrap();
+                    Ok(Self { core })
+                }
+                None => {
+                    lvgl_sys::lv_mem_free(map as *mut c_void);
+                    Err(LvError::InvalidReference)
+                }
+            }
+        }
+    }
+
+    /// Creates a message box on the active screen. See `Msgbox::create`.
+    pub fn new(
+        title: &CStr,
+        text: &CStr,
+        buttons: &[&'a CStr],
+        add_close_btn: bool,
+    ) -> LvResult<Self> {
+        let mut parent = crate::display::get_scr_act()?;
+        Self::create(&mut parent, title, text, buttons, add_close_btn)
+    }
+
+    /// Returns the index of the last pressed button, if any.
+    pub fn get_active_btn(&self) -> Option<u16> {
+        match unsafe { lvgl_sys::lv_msgbox_get_active_btn(self.core.raw().as_ptr()) } {
+            id if id as u32 == lvgl_sys::LV_BTNMATRIX_BTN_NONE => None,
+            id => Some(id),
+        }
+    }
+
+    /// Closes the message box, deleting it along with its children.
+    pub fn close(self) {
+        unsafe { lvgl_sys::lv_msgbox_close(self.core.raw().as_ptr()) }
+    }
+}
+
+/// The empty string terminating the map of buttons.
+static END: c_char = 0;
+
+unsafe extern "C" fn free_map(event: *mut lvgl_sys::lv_event_t) {
+    lvgl_sys::lv_mem_free(lvgl_sys::lv_event_get_user_data(event));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input_device::pointer::{Pointer, PointerInputData};
+    use crate::input_device::InputDriver;
+    use crate::{Display, Event, Point};
+    use core::cell::Cell;
+    use cstr_core::CString;
+    use std::rc::Rc;
+
+    #[test]
+    fn click_msgbox_button() {
+        crate::tests::initialize_test(true);
+        let display = Display::default();
+        let yes = CString::new("Yes").unwrap();
+        let no = CString::new("No").unwrap();
+        let title = CString::new("Title").unwrap();
+        let text = CString::new("Text").unwrap();
+        let mut msgbox = Msgbox::new(&title, &text, &[&yes, &no], false).unwrap();
+        let clicked = Rc::new(Cell::new(None));
+        let received = clicked.clone();
+        msgbox
+            .add_event_handler(None, move |_, event, _| {
+                if let Event::Special(event) = event {
+                    received.set(Some(event));
+                }
+            })
+            .unwrap();
+
+        // Click the right half of the button row, i.e. the second button
+        let mut area = lvgl_sys::lv_area_t::default();
+        unsafe {
+            lvgl_sys::lv_obj_update_layout(msgbox.raw().as_ptr());
+            lvgl_sys::lv_obj_get_coords(
+                lvgl_sys::lv_msgbox_get_btns(msgbox.raw().as_ptr()),
+                &mut area,
+            );
+        }
+        let point = Point::new(
+            (area.x1 + (area.x2 - area.x1) * 3 / 4) as i32,
+            ((area.y1 + area.y2) / 2) as i32,
+        );
+        let mut reads = 0;
+        let _pointer = Pointer::register(
+            move || {
+                reads += 1;
+                match reads {
+                    1 => PointerInputData::Touch(point).pressed().once(),
+                    _ => PointerInputData::Touch(point).released().once(),
+                }
+            },
+            &display,
+        )
+        .unwrap();
+        for _ in 0..3 {
+            unsafe {
+                lvgl_sys::lv_tick_inc(lvgl_sys::LV_INDEV_DEF_READ_PERIOD);
+                lvgl_sys::lv_timer_handler();
+            }
+        }
+
+        assert_eq!(clicked.get(), Some(MsgboxEvent::ButtonClicked(1)));
+        assert_eq!(msgbox.get_active_btn(), Some(1));
+        msgbox.close();
+    }
+}
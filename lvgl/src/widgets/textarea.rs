@@ -0,0 +1,62 @@
+use crate::SpecialEvent;
+
+/// Special events of a `Textarea`.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub enum TextareaEvent {
+    /// Text is about to be inserted.
+    Insert,
+    /// Enter was pressed in one-line mode, or the OK key of an associated
+    /// keyboard was pressed.
+    Ready,
+}
+
+impl SpecialEvent for TextareaEvent {
+    unsafe fn from_raw(event: *mut lvgl_sys::lv_event_t) -> Option<Self> {
+        match lvgl_sys::lv_event_get_code(event) {
+            lvgl_sys::lv_event_code_t_LV_EVENT_INSERT => Some(TextareaEvent::Insert),
+            lvgl_sys::lv_event_code_t_LV_EVENT_READY => Some(TextareaEvent::Ready),
+            _ => None,
+        }
+    }
+
+    fn code(&self) -> lvgl_sys::lv_event_code_t {
+        match self {
+            TextareaEvent::Insert => lvgl_sys::lv_event_code_t_LV_EVENT_INSERT,
+            TextareaEvent::Ready => lvgl_sys::lv_event_code_t_LV_EVENT_READY,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::widgets::Textarea;
+    use crate::{event_send, Event, NativeObject, Widget};
+    use core::cell::RefCell;
+    use cstr_core::CString;
+    use std::rc::Rc;
+
+    #[test]
+    fn textarea_special_events() {
+        crate::tests::initialize_test(true);
+        let mut textarea = Textarea::new().unwrap();
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let received = events.clone();
+        textarea
+            .add_event_handler(None, move |_, event, _| {
+                if let Event::Special(event) = event {
+                    received.borrow_mut().push(event);
+                }
+            })
+            .unwrap();
+
+        let text = CString::new("a").unwrap();
+        unsafe { lvgl_sys::lv_textarea_add_text(textarea.raw().as_ptr(), text.as_ptr()) };
+        event_send(&mut textarea, Event::Special(TextareaEvent::Ready)).unwrap();
+        assert_eq!(
+            *events.borrow(),
+            [TextareaEvent::Insert, TextareaEvent::Ready]
+        );
+        unsafe { lvgl_sys::lv_obj_del(textarea.raw().as_ptr()) };
+    }
+}